@@ -0,0 +1,224 @@
+#![allow(dead_code)]
+
+use std::{error::Error, fmt};
+
+use crate::{
+    bits::Bits, family::Family, function::MonotoneFunction, interval::Interval, util::binomial,
+};
+
+#[derive(Debug)]
+pub struct NotAntichainError;
+
+impl fmt::Display for NotAntichainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NotAntichainError")
+    }
+}
+
+impl Error for NotAntichainError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+/// Family in which no member lies below another.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Antichain<const N: usize> {
+    members: Family<N>,
+}
+
+impl<const N: usize> Antichain<N> {
+    pub fn new(members: Family<N>) -> Result<Self, NotAntichainError> {
+        if members.is_antichain() {
+            Ok(Self { members })
+        } else {
+            Err(NotAntichainError)
+        }
+    }
+
+    pub fn family(&self) -> &Family<N> {
+        &self.members
+    }
+
+    pub fn into_family(self) -> Family<N> {
+        self.members
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Members in graded order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Bits<N>> + '_ {
+        self.members.iter()
+    }
+
+    /// Sum over the members of one over the size of their layer, which the
+    /// LYM inequality bounds by one.
+    pub fn lym(&self) -> f64 {
+        self.iter()
+            .map(|a| 1.0 / binomial(N, a.count_ones()) as f64)
+            .sum()
+    }
+
+    /// Size of the largest antichain of the cube, its middle layer (Sperner).
+    pub fn width() -> u128 {
+        binomial(N, N / 2)
+    }
+
+    /// Whether every point of the cube lies above or below some member.
+    pub fn is_maximal(&self) -> bool {
+        self.is_maximal_in(&Interval::cube())
+    }
+
+    /// Whether the members lie in `interval` and every point of it lies
+    /// above or below some member. A point `x` comparable to no member has
+    /// a minimal transversal of the members below its complement, so it is
+    /// enough that each minimal transversal, together with some member,
+    /// covers every free coordinate.
+    pub fn is_maximal_in(&self, interval: &Interval<N>) -> bool {
+        if !self.iter().all(|a| interval.contains(&a)) {
+            return false;
+        }
+
+        let free = interval.free();
+        let members: Family<N> = self.iter().map(|a| (a ^ interval.lo()) & free).collect();
+
+        members
+            .blocker()
+            .iter()
+            .all(|t| members.iter().any(|a| (a | t) & free == free))
+    }
+}
+
+impl<const N: usize> TryFrom<Family<N>> for Antichain<N> {
+    type Error = NotAntichainError;
+
+    fn try_from(members: Family<N>) -> Result<Self, Self::Error> {
+        Self::new(members)
+    }
+}
+
+impl<const N: usize> TryFrom<Vec<Bits<N>>> for Antichain<N> {
+    type Error = NotAntichainError;
+
+    fn try_from(members: Vec<Bits<N>>) -> Result<Self, Self::Error> {
+        Self::new(Family::from(members))
+    }
+}
+
+impl<const N: usize> From<Antichain<N>> for Family<N> {
+    fn from(antichain: Antichain<N>) -> Self {
+        antichain.members
+    }
+}
+
+impl<const N: usize> From<Antichain<N>> for MonotoneFunction<N> {
+    fn from(antichain: Antichain<N>) -> Self {
+        MonotoneFunction::new(antichain.iter().collect())
+    }
+}
+
+impl<const N: usize> MonotoneFunction<N> {
+    /// Minimal implicants, which always form an antichain.
+    pub fn antichain(&self) -> Antichain<N> {
+        Antichain {
+            members: self.family().clone(),
+        }
+    }
+}
+
+/// Every maximal antichain of an interval of dimension at most 7, as the
+/// maximal cliques of its incomparability graph, found by Bron and
+/// Kerbosch's algorithm with pivoting.
+#[derive(Clone, Debug)]
+pub struct MaximalAntichains<const N: usize> {
+    points: Vec<Bits<N>>,
+    incomparable: Vec<u128>,
+    // Chosen, candidate and excluded points of each pending branch
+    pending: Vec<(u128, u128, u128)>,
+}
+
+impl<const N: usize> MaximalAntichains<N> {
+    fn new(interval: &Interval<N>) -> Self {
+        assert!(
+            interval.dimension() <= 7,
+            "maximal antichains are limited to 7 free bits"
+        );
+
+        let points: Vec<Bits<N>> = interval.iter().collect();
+        let incomparable = points
+            .iter()
+            .map(|x| {
+                points
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, y)| x.partial_cmp(y).is_none())
+                    .fold(0, |mask, (j, _)| mask | 1 << j)
+            })
+            .collect();
+        let everything = u128::MAX >> (128 - points.len());
+
+        Self {
+            points,
+            incomparable,
+            pending: vec![(0, everything, 0)],
+        }
+    }
+}
+
+impl<const N: usize> Iterator for MaximalAntichains<N> {
+    type Item = Antichain<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((chosen, mut candidates, mut excluded)) = self.pending.pop() {
+            if candidates == 0 && excluded == 0 {
+                let members = (0..self.points.len())
+                    .filter(|j| chosen >> j & 1 == 1)
+                    .map(|j| self.points[j])
+                    .collect();
+
+                return Some(Antichain { members });
+            }
+
+            let pivot = (0..self.points.len())
+                .filter(|j| (candidates | excluded) >> j & 1 == 1)
+                .max_by_key(|j| (candidates & self.incomparable[*j]).count_ones())
+                .unwrap();
+            let branches = candidates & !self.incomparable[pivot];
+
+            for v in (0..self.points.len()).filter(|j| branches >> j & 1 == 1) {
+                let neighbours = self.incomparable[v];
+
+                self.pending.push((
+                    chosen | 1 << v,
+                    candidates & neighbours,
+                    excluded & neighbours,
+                ));
+                candidates &= !(1 << v);
+                excluded |= 1 << v;
+            }
+        }
+
+        None
+    }
+}
+
+impl<const N: usize> Antichain<N> {
+    /// Every maximal antichain of the cube. Panics for `N > 7`.
+    pub fn maximal_antichains() -> MaximalAntichains<N> {
+        MaximalAntichains::new(&Interval::cube())
+    }
+}
+
+impl<const N: usize> Interval<N> {
+    /// Every maximal antichain of the interval. Panics if its dimension
+    /// exceeds 7.
+    pub fn maximal_antichains(&self) -> MaximalAntichains<N> {
+        MaximalAntichains::new(self)
+    }
+}
@@ -1,12 +1,12 @@
 #![allow(dead_code)]
 
-use itertools::{Combinations, Itertools};
-use rand::{thread_rng, Rng};
+use rand::{seq::SliceRandom, thread_rng, Rng};
 use std::{
+    borrow::Cow,
     cmp::Ordering,
-    collections::{HashSet, VecDeque},
     error::Error,
     fmt,
+    hash::{Hash, Hasher},
     ops::{
         BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index, IndexMut, Not, Shl,
         ShlAssign, Shr, ShrAssign,
@@ -14,475 +14,299 @@ use std::{
     str::FromStr,
 };
 
-use crate::util::rand_combination;
+use crate::{
+    interval::{Interval, Points},
+    packed::{self, next_permutation, Packed},
+    rank::Layer,
+    util::{factorial, rand_combination_with},
+};
+
+pub type Zeroes<const N: usize> = packed::Zeroes<Bits<N>>;
+pub type Ones<const N: usize> = packed::Ones<Bits<N>>;
+pub type UpperShadow<const N: usize> = packed::UpperShadow<Bits<N>>;
+pub type LowerShadow<const N: usize> = packed::LowerShadow<Bits<N>>;
+pub type Midpoints<const N: usize> = packed::Midpoints<Bits<N>>;
+pub type Horizon<const N: usize> = packed::Horizon<Bits<N>>;
+pub type Converge<const N: usize> = packed::Converge<Bits<N>>;
+pub type Paths<const N: usize> = packed::Paths<Bits<N>>;
 
 #[derive(Debug)]
-pub struct Zeroes<const N: usize> {
-    bits: Bits<N>,
-    cursor: usize,
-}
+pub struct IncomparableError;
 
-impl<const N: usize> Zeroes<N> {
-    fn new(bits: &Bits<N>) -> Self {
-        Self {
-            bits: *bits,
-            cursor: 0,
-        }
+impl fmt::Display for IncomparableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IncomparableError")
     }
 }
 
-impl<const N: usize> Iterator for Zeroes<N> {
-    type Item = usize;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        while self.cursor < self.bits.len() {
-            let i = self.cursor;
-
-            if !self.bits[i] {
-                self.cursor += 1;
-                return Some(i);
-            }
-
-            self.cursor += 1;
-        }
-
+impl Error for IncomparableError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
         None
     }
 }
 
-#[derive(Debug)]
-pub struct Ones<const N: usize> {
-    inner: Zeroes<N>,
+/// Maximal chains in lexicographic order of the sequence of flipped
+/// coordinates, i.e. in `path_rank` order, written into one reused buffer.
+/// Each step rewrites only the suffix after the first changed flip, and
+/// nothing is allocated after construction.
+#[derive(Clone, Debug)]
+pub struct PathWalk<const N: usize> {
+    order: Vec<usize>,
+    buffer: Vec<Bits<N>>,
+    started: bool,
 }
 
-impl<const N: usize> Ones<N> {
-    fn new(bits: &Bits<N>) -> Self {
-        Self {
-            inner: Zeroes::new(&!*bits),
-        }
-    }
-}
+impl<const N: usize> PathWalk<N> {
+    fn new(start: &Bits<N>, end: &Bits<N>) -> Result<Self, IncomparableError> {
+        let order = Vec::from_iter((*start ^ *end).ones());
+        let mut walk = Self {
+            buffer: vec![*start; order.len() + 1],
+            order,
+            started: false,
+        };
 
-impl<const N: usize> Iterator for Ones<N> {
-    type Item = usize;
+        start.distance(end)?;
+        walk.fill(0);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
+        Ok(walk)
     }
-}
 
-#[derive(Debug)]
-pub struct UpperShadow<const N: usize> {
-    inner: Converge<N>,
-}
+    /// Recomputes the buffer after the first `from` flips.
+    fn fill(&mut self, from: usize) {
+        for j in from..self.order.len() {
+            let mut next = self.buffer[j];
 
-impl<const N: usize> UpperShadow<N> {
-    fn new(origin: &Bits<N>) -> Self {
-        Self {
-            inner: Converge::new(origin, &Bits::new(true)).unwrap(),
+            next.set(self.order[j], !next.get(self.order[j]));
+            self.buffer[j + 1] = next;
         }
     }
-}
-
-impl<const N: usize> Iterator for UpperShadow<N> {
-    type Item = Bits<N>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
-    }
-}
 
-#[derive(Debug)]
-pub struct LowerShadow<const N: usize> {
-    inner: UpperShadow<N>,
-}
+    /// The next path, from `start` to `end`, valid until the next call.
+    pub fn next_path(&mut self) -> Option<&[Bits<N>]> {
+        if !self.started {
+            self.started = true;
 
-impl<const N: usize> LowerShadow<N> {
-    fn new(origin: &Bits<N>) -> Self {
-        Self {
-            inner: UpperShadow::new(&!*origin),
+            return Some(&self.buffer);
         }
-    }
-}
-
-impl<const N: usize> Iterator for LowerShadow<N> {
-    type Item = Bits<N>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next().map(|next| !next)
-    }
-}
 
-#[derive(Debug)]
-pub struct IncomparableError;
+        let pivot = next_permutation(&mut self.order)?;
 
-impl fmt::Display for IncomparableError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "IncomparableError")
-    }
-}
+        self.fill(pivot);
 
-impl Error for IncomparableError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        None
+        Some(&self.buffer)
     }
 }
 
-#[derive(Debug)]
-pub struct Midpoints<const N: usize> {
-    origin: Bits<N>,
-    combs_lower: Combinations<Ones<N>>,
-    combs_upper: Combinations<Ones<N>>,
+/// de Bruijn, Tengbergen and Kruyswijk's symmetric chain decomposition of an
+/// interval, built by bracket matching (Greene and Kleitman): reading the
+/// free coordinates in order, a clear bit opens a bracket and a set bit
+/// closes one. A chain starts at each point with no unmatched set bit and
+/// climbs by setting its unmatched clear bits from left to right, so it runs
+/// from `r` steps above the bottom up to `r` steps below the top.
+#[derive(Clone, Debug)]
+pub struct SymmetricChains<const N: usize> {
+    free: Vec<usize>,
+    points: Points<N>,
 }
 
-impl<const N: usize> Midpoints<N> {
-    fn new(start: &Bits<N>, end: &Bits<N>) -> Result<Self, IncomparableError> {
-        if start.partial_cmp(end).is_none() {
-            return Err(IncomparableError);
+impl<const N: usize> SymmetricChains<N> {
+    pub(crate) fn new(interval: &Interval<N>) -> Self {
+        Self {
+            free: Vec::from_iter(interval.free().ones()),
+            points: interval.iter(),
         }
+    }
 
-        let origin: Bits<N>;
-        let target: Bits<N>;
+    /// The chain starting at `origin`, or `None` if `origin` has a set bit
+    /// left unmatched and so lies inside some other chain.
+    fn chain_from(&self, origin: Bits<N>) -> Option<Vec<Bits<N>>> {
+        let mut open = Vec::new();
 
-        if *start > *end {
-            origin = *end;
-            target = *start;
-        } else {
-            origin = *start;
-            target = *end;
+        for i in &self.free {
+            if !origin.get(*i) {
+                open.push(*i);
+            } else if open.pop().is_none() {
+                return None;
+            }
         }
 
-        let diff = origin ^ target;
-        let size = diff.count_ones();
-        let combs_lower: Combinations<Ones<N>>;
-        let combs_upper: Combinations<Ones<N>>;
-
-        if size % 2 == 0 {
-            let count = size / 2;
+        let mut cursor = origin;
+        let mut chain = Vec::with_capacity(open.len() + 1);
 
-            combs_lower = diff.ones().combinations(count);
-            // TODO might be more efficient to use an enum here
-            combs_upper = diff.ones().combinations(0);
-        } else {
-            let count_lower = size / 2;
-            let count_upper = (size / 2) + 1;
+        chain.push(cursor);
 
-            combs_lower = diff.ones().combinations(count_lower);
-            combs_upper = diff.ones().combinations(count_upper);
+        for i in open {
+            cursor.set(i, true);
+            chain.push(cursor);
         }
 
-        Ok(Self {
-            origin,
-            combs_lower,
-            combs_upper,
-        })
+        Some(chain)
     }
 }
 
-impl<const N: usize> Iterator for Midpoints<N> {
-    type Item = Bits<N>;
+impl<const N: usize> Iterator for SymmetricChains<N> {
+    type Item = Vec<Bits<N>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(ones) = self.combs_lower.next() {
-            let mut next = self.origin;
+        loop {
+            let origin = self.points.next()?;
 
-            for i in ones {
-                next[i] = true;
-            }
-
-            return Some(next);
-        } else if let Some(ones) = self.combs_upper.next() {
-            if ones.is_empty() {
-                return None;
+            if let Some(chain) = self.chain_from(origin) {
+                return Some(chain);
             }
-
-            let mut next = self.origin;
-
-            for i in ones {
-                next[i] = true;
-            }
-
-            return Some(next);
         }
-
-        None
     }
 }
 
-#[derive(Debug)]
-enum HorizonIndices<const N: usize> {
-    Upper(Zeroes<N>),
-    Lower(Ones<N>),
+/// Storage for `N` bits as `N.div_ceil(64)` limbs. An array of that length
+/// would need `generic_const_exprs`, so the limbs are laid over a word
+/// followed by `N` bytes, which always covers them since
+/// `8 * N.div_ceil(64) <= 8 + N`.
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct LimbArray<const N: usize> {
+    head: u64,
+    tail: [u8; N],
 }
 
-#[derive(Debug)]
-pub struct Horizon<const N: usize> {
-    origin: Bits<N>,
-    indices: HorizonIndices<N>,
-    lower: bool,
-}
-
-impl<const N: usize> Horizon<N> {
-    fn new(origin: &Bits<N>, lower: bool) -> Self {
-        let indices = if lower {
-            HorizonIndices::Lower(origin.ones())
-        } else {
-            HorizonIndices::Upper(origin.zeroes())
-        };
+impl<const N: usize> LimbArray<N> {
+    const WORDS: usize = N.div_ceil(64);
 
+    fn new() -> Self {
         Self {
-            origin: *origin,
-            indices,
-            lower,
+            head: 0,
+            tail: [0; N],
         }
     }
-}
-
-impl<const N: usize> Iterator for Horizon<N> {
-    type Item = Bits<N>;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let maybe_index = match self.indices {
-            HorizonIndices::Upper(ref mut zeroes) => zeroes.next(),
-            HorizonIndices::Lower(ref mut ones) => ones.next(),
-        };
-
-        if let Some(i) = maybe_index {
-            let mut next = self.origin;
-
-            if self.lower {
-                next[i] = false;
-            } else {
-                next[i] = true;
-            }
+    fn as_slice(&self) -> &[u64] {
+        // SAFETY: with `repr(C)` the struct is aligned for `u64` and `tail`
+        // follows `head` without padding, so the first `8 + N` bytes are
+        // initialised and hold the `WORDS` limbs
+        unsafe { std::slice::from_raw_parts(self as *const Self as *const u64, Self::WORDS) }
+    }
 
-            Some(next)
-        } else {
-            None
-        }
+    fn as_mut_slice(&mut self) -> &mut [u64] {
+        // SAFETY: as in `as_slice`, and every bit pattern is a valid `u8`
+        unsafe { std::slice::from_raw_parts_mut(self as *mut Self as *mut u64, Self::WORDS) }
     }
 }
 
-#[derive(Debug)]
-pub struct Converge<const N: usize> {
-    cursor: Bits<N>,
-    origin: Bits<N>,
-    target: Bits<N>,
-    seen: HashSet<Bits<N>>,
-    pending: VecDeque<Horizon<N>>,
-    reversed: bool,
-    initialized: bool,
+#[derive(Clone, Copy)]
+pub struct Bits<const N: usize> {
+    limbs: LimbArray<N>,
+    // `IndexMut` has to hand out a `&mut bool`, so a write through it lands
+    // in `slot_value` and is folded into `limbs` on the next mutable access
+    slot: Option<usize>,
+    slot_value: bool,
 }
 
-impl<const N: usize> Converge<N> {
-    fn new(start: &Bits<N>, end: &Bits<N>) -> Result<Self, IncomparableError> {
-        if start.partial_cmp(end).is_none() {
-            return Err(IncomparableError);
-        }
-
-        let origin = *start;
-        let target = *end;
-        let cursor = origin;
-        let seen = HashSet::<Bits<N>>::new();
-        let mut pending = VecDeque::<Horizon<N>>::new();
-        let reversed = origin > target;
-        let initialized = false;
-
-        pending.push_back(Horizon::new(&origin, reversed));
-
-        Ok(Self {
-            cursor,
-            origin,
-            target,
-            seen,
-            pending,
-            reversed,
-            initialized,
-        })
+impl<const N: usize> Packed for Bits<N> {
+    fn len(&self) -> usize {
+        N
     }
-}
-
-impl<const N: usize> Iterator for Converge<N> {
-    type Item = Bits<N>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if !self.initialized {
-            self.initialized = true;
-
-            return Some(self.origin);
-        } else if self.origin == self.target {
-            return None;
-        }
-
-        while let Some(mut horizon) = self.pending.pop_front() {
-            while let Some(next) = horizon.next() {
-                if self.seen.contains(&next) {
-                    continue;
-                }
 
-                self.pending.push_back(Horizon::new(&next, self.reversed));
-                self.pending.push_front(horizon);
-                self.seen.insert(next);
+    /// Borrowed unless an `IndexMut` write is still pending, in which case
+    /// a copy with it applied.
+    fn limbs(&self) -> Cow<'_, [u64]> {
+        match self.slot {
+            None => Cow::Borrowed(self.limbs.as_slice()),
+            Some(i) => {
+                let mut limbs = self.limbs.as_slice().to_vec();
 
-                return Some(next);
+                packed::set(&mut limbs, i, self.slot_value);
+                Cow::Owned(limbs)
             }
         }
-
-        None
     }
-}
 
-type Path<const N: usize> = Vec<Bits<N>>;
+    fn limbs_mut(&mut self) -> &mut [u64] {
+        if let Some(i) = self.slot.take() {
+            packed::set(self.limbs.as_mut_slice(), i, self.slot_value);
+        }
 
-#[derive(Debug)]
-pub struct Paths<const N: usize> {
-    origin: Bits<N>,
-    target: Bits<N>,
-    pending: VecDeque<(Bits<N>, Path<N>)>,
-    reversed: bool,
-    done: bool,
+        self.limbs.as_mut_slice()
+    }
 }
 
-/// Depth-first paths
-impl<const N: usize> Paths<N> {
-    fn new(start: &Bits<N>, end: &Bits<N>) -> Result<Self, IncomparableError> {
-        if start.partial_cmp(end).is_none() {
-            return Err(IncomparableError);
-        }
-
-        let reversed: bool;
-        let origin: Bits<N>;
-        let target: Bits<N>;
+impl<const N: usize> Bits<N> {
+    /// Bits from `limbs`, ignoring any past `N`.
+    pub(crate) fn from_limbs(limbs: &[u64]) -> Self {
+        let mut bits = Self::new(false);
 
-        if start > end {
-            reversed = true;
-            origin = *end;
-            target = *start;
-        } else {
-            reversed = false;
-            origin = *start;
-            target = *end;
+        for (limb, value) in bits.limbs_mut().iter_mut().zip(limbs) {
+            *limb = *value;
         }
 
-        let base = vec![origin];
-        let diff = origin ^ target;
-        let mut pending: VecDeque<(Bits<N>, Path<N>)> = VecDeque::new();
+        packed::trim(bits.limbs_mut(), N);
+        bits
+    }
 
-        for i in diff.ones() {
-            let mut next = origin;
-            next[i] = true;
-            pending.push_back((next, base.clone()));
+    fn zip_with(mut self, rhs: Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        for (a, b) in self.limbs_mut().iter_mut().zip(rhs.limbs().iter()) {
+            *a = f(*a, *b);
         }
 
-        Ok(Self {
-            origin,
-            target,
-            pending,
-            reversed,
-            done: false,
-        })
+        packed::trim(self.limbs_mut(), N);
+        self
     }
-}
 
-impl<const N: usize> Iterator for Paths<N> {
-    type Item = Path<N>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.origin == self.target && !self.done {
-            self.done = true;
+    pub fn new(value: bool) -> Self {
+        let bits = Self {
+            limbs: LimbArray::new(),
+            slot: None,
+            slot_value: false,
+        };
 
-            return Some(vec![self.origin]);
-        } else if self.pending.is_empty() || self.done {
-            return None;
+        if value {
+            packed::filled(&bits, true)
+        } else {
+            bits
         }
+    }
 
-        let (cursor, mut base) = self.pending.pop_front().unwrap();
-        base.push(cursor);
-
-        if cursor == self.target {
-            if self.reversed {
-                base.reverse();
-            }
-
-            return Some(base);
-        }
+    pub fn len(&self) -> usize {
+        N
+    }
 
-        let diff = cursor ^ self.target;
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < N, "index {} out of range for Bits<{}>", i, N);
 
-        for i in diff.ones() {
-            let mut next = cursor;
-            next[i] = true;
-            self.pending.push_front((next, base.clone()));
+        if self.slot == Some(i) {
+            self.slot_value
+        } else {
+            packed::get(self.limbs.as_slice(), i)
         }
-
-        self.next()
     }
-}
 
-#[derive(Clone, Copy, Eq, Hash, PartialEq)]
-pub struct Bits<const N: usize> {
-    inner: [bool; N],
-}
-
-impl<const N: usize> Bits<N> {
-    pub fn new(value: bool) -> Self {
-        Self { inner: [value; N] }
-    }
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < N, "index {} out of range for Bits<{}>", i, N);
 
-    pub fn len(&self) -> usize {
-        self.inner.len()
+        packed::set(self.limbs_mut(), i, value);
     }
 
     pub fn count_zeroes(&self) -> usize {
-        let mut count = 0_usize;
-
-        for i in 0..self.len() {
-            if !self[i] {
-                count += 1;
-            }
-        }
-
-        count
+        N - self.count_ones()
     }
 
     pub fn count_ones(&self) -> usize {
-        let mut count = 0_usize;
-
-        for i in 0..self.len() {
-            if self[i] {
-                count += 1;
-            }
-        }
-
-        count
+        packed::count_ones(&self.limbs())
     }
 
     pub fn zeroes(&self) -> Zeroes<N> {
-        Zeroes::new(self)
+        packed::Zeroes::new(self)
     }
 
     pub fn ones(&self) -> Ones<N> {
-        Ones::new(self)
+        packed::Ones::new(self)
     }
 
     pub fn and(&self) -> bool {
-        for i in 0..self.len() {
-            if !self[i] {
-                return false;
-            }
-        }
-
-        true
+        self.count_ones() == N
     }
 
     pub fn or(&self) -> bool {
-        for i in 0..self.len() {
-            if self[i] {
-                return true;
-            }
-        }
-
-        false
+        self.limbs().iter().any(|limb| *limb != 0)
     }
 
     pub fn distance(&self, other: &Bits<N>) -> Result<usize, IncomparableError> {
@@ -494,55 +318,156 @@ impl<const N: usize> Bits<N> {
     }
 
     pub fn rand_midpoint(&self, other: &Bits<N>) -> Result<Bits<N>, IncomparableError> {
-        if self.partial_cmp(other).is_none() {
-            return Err(IncomparableError);
+        self.rand_midpoint_with(other, &mut thread_rng())
+    }
+
+    pub fn rand_midpoint_with(
+        &self,
+        other: &Bits<N>,
+        rng: &mut impl Rng,
+    ) -> Result<Bits<N>, IncomparableError> {
+        match self.partial_cmp(other) {
+            None => Err(IncomparableError),
+            Some(Ordering::Greater) => Ok(Self::rand_midpoint_between(other, self, rng)),
+            Some(_) => Ok(Self::rand_midpoint_between(self, other, rng)),
         }
+    }
 
-        let origin = if *self > *other { *other } else { *self };
-        let diff = *self ^ *other;
+    /// `rand_midpoint_with` for `lo` lying below `hi`, which is not checked.
+    pub(crate) fn rand_midpoint_between(lo: &Bits<N>, hi: &Bits<N>, rng: &mut impl Rng) -> Self {
+        let diff = *lo ^ *hi;
         let size = diff.count_ones();
-        let mut rng = thread_rng();
-
         // Account for cases where number of ones is not even
-        let count = if size % 2 == 0 || rng.gen_bool(0.5) {
+        let count = if size.is_multiple_of(2) || rng.gen_bool(0.5) {
             size / 2
         } else {
             (size / 2) + 1
         };
 
         let ones = Vec::from_iter(diff.ones());
-        let indices = rand_combination(&ones, count);
-        let mut target = origin;
+        let indices = rand_combination_with(&ones, count, rng);
+        let mut target = *lo;
 
         for i in indices {
-            target[i] = true;
+            target.set(i, true);
+        }
+
+        target
+    }
+
+    /// Number of maximal chains between `self` and `other`, `d!` for
+    /// distance `d`. Panics if that overflows a `u128`.
+    pub fn count_paths(&self, other: &Bits<N>) -> Result<u128, IncomparableError> {
+        Ok(factorial(self.distance(other)?))
+    }
+
+    pub fn rand_path(&self, other: &Bits<N>) -> Result<Vec<Bits<N>>, IncomparableError> {
+        self.rand_path_with(other, &mut thread_rng())
+    }
+
+    /// Uniform maximal chain from `self` to `other`.
+    pub fn rand_path_with(
+        &self,
+        other: &Bits<N>,
+        rng: &mut impl Rng,
+    ) -> Result<Vec<Bits<N>>, IncomparableError> {
+        let mut order = Vec::from_iter((*self ^ *other).ones());
+        let mut cursor = *self;
+        let mut path = vec![cursor];
+
+        self.distance(other)?;
+        order.shuffle(rng);
+
+        for i in order {
+            cursor.set(i, !cursor.get(i));
+            path.push(cursor);
         }
 
-        Ok(target)
+        Ok(path)
     }
 
     pub fn horizon(&self, lower: bool) -> Horizon<N> {
-        Horizon::new(self, lower)
+        packed::Horizon::new(self, lower)
     }
 
     pub fn midpoints(&self, other: &Bits<N>) -> Result<Midpoints<N>, IncomparableError> {
-        Midpoints::new(self, other)
+        packed::Midpoints::new(self, other)
+    }
+
+    /// The layer `floor(d / 2)` steps above the bottom of the interval
+    /// spanned by `self` and `other`, for distance `d`; the lower middle
+    /// layer when `d` is odd.
+    pub fn middle_layer(&self, other: &Bits<N>) -> Result<Layer<N>, IncomparableError> {
+        self.quantile_layer(other, 0.5)
+    }
+
+    /// The layer a fraction `q` of the way up the interval spanned by `self`
+    /// and `other`, i.e. `interval_layer` with `k = floor(q * d)` for
+    /// distance `d`, whichever way round the points are given, and like it
+    /// defined for any width. Panics unless `0 <= q <= 1`.
+    pub fn quantile_layer(&self, other: &Bits<N>, q: f64) -> Result<Layer<N>, IncomparableError> {
+        assert!((0.0..=1.0).contains(&q), "quantile must lie in [0, 1]");
+
+        let d = self.distance(other)?;
+
+        self.interval_layer(other, (q * d as f64).floor() as usize)
     }
 
     pub fn converge(&self, other: &Bits<N>) -> Result<Converge<N>, IncomparableError> {
-        Converge::new(self, other)
+        packed::Converge::new(self, other)
+    }
+
+    /// `converge` picked up at a position saved from `Converge::position`.
+    pub fn converge_from(
+        &self,
+        other: &Bits<N>,
+        position: (usize, u128),
+    ) -> Result<Converge<N>, IncomparableError> {
+        packed::Converge::resume(self, other, position)
     }
 
     pub fn paths(&self, other: &Bits<N>) -> Result<Paths<N>, IncomparableError> {
-        Paths::new(self, other)
+        packed::Paths::new(self, other)
+    }
+
+    pub fn path_walk(&self, other: &Bits<N>) -> Result<PathWalk<N>, IncomparableError> {
+        PathWalk::new(self, other)
+    }
+
+    /// Symmetric chain decomposition of the whole cube.
+    pub fn symmetric_chains() -> SymmetricChains<N> {
+        SymmetricChains::new(&Interval::cube())
+    }
+
+    /// Symmetric chain decomposition of the interval spanned by `self` and
+    /// `other`.
+    pub fn interval_symmetric_chains(
+        &self,
+        other: &Bits<N>,
+    ) -> Result<SymmetricChains<N>, IncomparableError> {
+        Ok(SymmetricChains::new(&Interval::new(self, other)?))
     }
 
     pub fn lower_shadow(&self) -> LowerShadow<N> {
-        LowerShadow::new(self)
+        packed::LowerShadow::new(self)
     }
 
     pub fn upper_shadow(&self) -> UpperShadow<N> {
-        UpperShadow::new(self)
+        packed::UpperShadow::new(self)
+    }
+}
+
+impl<const N: usize> PartialEq for Bits<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.limbs() == other.limbs()
+    }
+}
+
+impl<const N: usize> Eq for Bits<N> {}
+
+impl<const N: usize> Hash for Bits<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.limbs().hash(state);
     }
 }
 
@@ -560,13 +485,7 @@ impl<const N: usize> PartialOrd for Bits<N> {
     }
 
     fn le(&self, other: &Self) -> bool {
-        for i in 0..self.len() {
-            if self[i] && !other[i] {
-                return false;
-            }
-        }
-
-        true
+        packed::is_subset(&self.limbs(), &other.limbs())
     }
 }
 
@@ -603,19 +522,19 @@ impl<const N: usize> FromStr for Bits<N> {
             return Err(ParseBitsError::LengthMismatch);
         }
 
-        let mut inner: [bool; N] = [false; N];
+        let mut bits = Self::new(false);
 
         for (i, c) in s.chars().enumerate() {
             if c == '0' {
-                inner[i] = false;
+                bits.set(i, false);
             } else if c == '1' {
-                inner[i] = true;
+                bits.set(i, true);
             } else {
                 return Err(ParseBitsError::NonBinary);
             }
         }
 
-        Ok(Self { inner })
+        Ok(bits)
     }
 }
 
@@ -623,21 +542,13 @@ impl<const N: usize> BitAnd for Bits<N> {
     type Output = Self;
 
     fn bitand(self, rhs: Self) -> Self::Output {
-        let mut inner: [bool; N] = [false; N];
-
-        for i in 0..self.len() {
-            inner[i] = self[i] & rhs[i];
-        }
-
-        Self { inner }
+        self.zip_with(rhs, |a, b| a & b)
     }
 }
 
 impl<const N: usize> BitAndAssign for Bits<N> {
     fn bitand_assign(&mut self, rhs: Self) {
-        for i in 0..self.len() {
-            self.inner[i] = self[i] & rhs[i];
-        }
+        *self = *self & rhs;
     }
 }
 
@@ -645,21 +556,13 @@ impl<const N: usize> BitOr for Bits<N> {
     type Output = Self;
 
     fn bitor(self, rhs: Self) -> Self::Output {
-        let mut inner: [bool; N] = [false; N];
-
-        for i in 0..self.len() {
-            inner[i] = self[i] | rhs[i];
-        }
-
-        Self { inner }
+        self.zip_with(rhs, |a, b| a | b)
     }
 }
 
 impl<const N: usize> BitOrAssign for Bits<N> {
     fn bitor_assign(&mut self, rhs: Self) {
-        for i in 0..self.len() {
-            self.inner[i] = self[i] | rhs[i];
-        }
+        *self = *self | rhs;
     }
 }
 
@@ -667,21 +570,13 @@ impl<const N: usize> BitXor for Bits<N> {
     type Output = Self;
 
     fn bitxor(self, rhs: Self) -> Self::Output {
-        let mut inner: [bool; N] = [false; N];
-
-        for i in 0..self.len() {
-            inner[i] = self[i] ^ rhs[i];
-        }
-
-        Self { inner }
+        self.zip_with(rhs, |a, b| a ^ b)
     }
 }
 
 impl<const N: usize> BitXorAssign for Bits<N> {
     fn bitxor_assign(&mut self, rhs: Self) {
-        for i in 0..self.len() {
-            self.inner[i] = self[i] ^ rhs[i];
-        }
+        *self = *self ^ rhs;
     }
 }
 
@@ -689,13 +584,25 @@ impl<const N: usize> Index<usize> for Bits<N> {
     type Output = bool;
 
     fn index(&self, i: usize) -> &Self::Output {
-        &self.inner[i]
+        if self.slot == Some(i) {
+            &self.slot_value
+        } else if self.get(i) {
+            &true
+        } else {
+            &false
+        }
     }
 }
 
 impl<const N: usize> IndexMut<usize> for Bits<N> {
     fn index_mut(&mut self, i: usize) -> &mut Self::Output {
-        &mut self.inner[i]
+        let value = self.get(i);
+
+        self.limbs_mut();
+        self.slot = Some(i);
+        self.slot_value = value;
+
+        &mut self.slot_value
     }
 }
 
@@ -703,31 +610,18 @@ impl<const N: usize> Not for Bits<N> {
     type Output = Self;
 
     fn not(self) -> Self::Output {
-        let mut inner: [bool; N] = [false; N];
-
-        for i in 0..self.len() {
-            inner[i] = !self[i]
-        }
-
-        Self { inner }
+        packed::complement(&self)
     }
 }
 
 impl<const N: usize> Shl<usize> for Bits<N> {
     type Output = Self;
 
-    fn shl(self, rhs: usize) -> Self::Output {
-        let mut inner: [bool; N] = [false; N];
+    /// Moves every bit `rhs` places towards index 0.
+    fn shl(mut self, rhs: usize) -> Self::Output {
+        packed::shift_limbs_down(self.limbs_mut(), rhs);
 
-        if rhs >= self.len() {
-            Self { inner }
-        } else {
-            for i in 0..self.len() - rhs {
-                inner[i] = self[rhs + i];
-            }
-
-            Self { inner }
-        }
+        self
     }
 }
 
@@ -740,18 +634,11 @@ impl<const N: usize> ShlAssign<usize> for Bits<N> {
 impl<const N: usize> Shr<usize> for Bits<N> {
     type Output = Self;
 
-    fn shr(self, rhs: usize) -> Self::Output {
-        let mut inner: [bool; N] = [false; N];
-
-        if rhs >= self.len() {
-            Self { inner }
-        } else {
-            for i in 0..self.len() - rhs {
-                inner[rhs + i] = self[i]
-            }
-
-            Self { inner }
-        }
+    /// Moves every bit `rhs` places away from index 0.
+    fn shr(mut self, rhs: usize) -> Self::Output {
+        packed::shift_limbs_up(self.limbs_mut(), rhs);
+        packed::trim(self.limbs_mut(), N);
+        self
     }
 }
 
@@ -763,13 +650,7 @@ impl<const N: usize> ShrAssign<usize> for Bits<N> {
 
 impl<const N: usize> fmt::Debug for Bits<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut out: String = "".to_string();
-
-        for i in 0..self.len() {
-            out.push_str(if self[i] { "1" } else { "0" });
-        }
-
-        write!(f, "{}", out)
+        fmt::Display::fmt(self, f)
     }
 }
 
@@ -778,7 +659,7 @@ impl<const N: usize> fmt::Display for Bits<N> {
         let mut out: String = "".to_string();
 
         for i in 0..self.len() {
-            out.push_str(if self[i] { "1" } else { "0" });
+            out.push_str(if self.get(i) { "1" } else { "0" });
         }
 
         write!(f, "{}", out)
@@ -786,11 +667,34 @@ impl<const N: usize> fmt::Display for Bits<N> {
 }
 
 #[derive(Debug)]
-pub struct OverflowError;
+pub struct LengthMismatchError;
+
+impl fmt::Display for LengthMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LengthMismatchError")
+    }
+}
+
+impl Error for LengthMismatchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+/// A value that needs `width` bits where only `capacity` are available.
+#[derive(Debug)]
+pub struct OverflowError {
+    pub width: usize,
+    pub capacity: usize,
+}
 
 impl fmt::Display for OverflowError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "OverflowError")
+        write!(
+            f,
+            "OverflowError: {} bits do not fit in {}",
+            self.width, self.capacity
+        )
     }
 }
 
@@ -800,102 +704,187 @@ impl Error for OverflowError {
     }
 }
 
-impl<const N: usize> TryFrom<u8> for Bits<N> {
-    type Error = OverflowError;
+/// Which end of an integer lines up with index 0 of a `Bits<N>`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BitOrder {
+    /// The least significant bit sits at index `N - 1`, so `Bits` read like
+    /// the integer's binary literal. This is what the `TryFrom` impls use.
+    MsbFirst,
+    /// The least significant bit sits at index 0.
+    LsbFirst,
+}
 
-    fn try_from(x: u8) -> Result<Bits<N>, Self::Error> {
-        let mut bits = Bits::<N>::new(false);
+impl BitOrder {
+    /// Maps bit `p` of an integer to its index among `len` bits, and back.
+    fn index(self, len: usize, p: usize) -> usize {
+        match self {
+            BitOrder::MsbFirst => len - 1 - p,
+            BitOrder::LsbFirst => p,
+        }
+    }
+}
 
-        for i in 0..8 {
-            let bit = (x >> i) & 0x01 == 0x01;
+pub trait Unsigned: Copy {
+    const BITS: usize;
 
-            if i + 1 > N && bit {
-                return Err(OverflowError);
-            } else if bit {
-                bits[N - i - 1] = true;
-            }
-        }
+    fn to_u128(self) -> u128;
 
-        Ok(bits)
-    }
+    fn from_u128(x: u128) -> Self;
 }
 
-impl<const N: usize> TryFrom<u16> for Bits<N> {
-    type Error = OverflowError;
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl Unsigned for $t {
+                const BITS: usize = <$t>::BITS as usize;
+
+                fn to_u128(self) -> u128 {
+                    self as u128
+                }
+
+                fn from_u128(x: u128) -> Self {
+                    x as $t
+                }
+            }
+
+            impl<const N: usize> TryFrom<$t> for Bits<N> {
+                type Error = OverflowError;
 
-    fn try_from(x: u16) -> Result<Bits<N>, Self::Error> {
-        let mut bits = Bits::<N>::new(false);
+                fn try_from(x: $t) -> Result<Bits<N>, Self::Error> {
+                    Bits::from_uint(x, BitOrder::MsbFirst)
+                }
+            }
 
-        for i in 0..16 {
-            let bit = (x >> i) & 0x01 == 0x01;
+            impl<const N: usize> TryFrom<Bits<N>> for $t {
+                type Error = OverflowError;
 
-            if i + 1 > N && bit {
-                return Err(OverflowError);
-            } else if bit {
-                bits[N - i - 1] = true;
+                fn try_from(bits: Bits<N>) -> Result<$t, Self::Error> {
+                    bits.to_uint(BitOrder::MsbFirst)
+                }
             }
+        )*
+    };
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl<const N: usize> Bits<N> {
+    pub fn from_uint<T: Unsigned>(x: T, order: BitOrder) -> Result<Self, OverflowError> {
+        let mut x = x.to_u128();
+        let width = 128 - x.leading_zeros() as usize;
+
+        if width > N {
+            return Err(OverflowError { width, capacity: N });
+        }
+
+        let mut bits = Self::new(false);
+
+        while x != 0 {
+            bits.set(order.index(N, x.trailing_zeros() as usize), true);
+            x &= x - 1;
         }
 
         Ok(bits)
     }
-}
 
-impl<const N: usize> TryFrom<u32> for Bits<N> {
-    type Error = OverflowError;
+    pub fn to_uint<T: Unsigned>(self, order: BitOrder) -> Result<T, OverflowError> {
+        let width = self
+            .ones()
+            .map(|i| order.index(N, i) + 1)
+            .max()
+            .unwrap_or(0);
+
+        if width > T::BITS {
+            return Err(OverflowError {
+                width,
+                capacity: T::BITS,
+            });
+        }
 
-    fn try_from(x: u32) -> Result<Bits<N>, Self::Error> {
-        let mut bits = Bits::<N>::new(false);
+        let x = self
+            .ones()
+            .fold(0_u128, |x, i| x | (1 << order.index(N, i)));
 
-        for i in 0..32 {
-            let bit = (x >> i) & 0x01 == 0x01;
+        Ok(T::from_u128(x))
+    }
+
+    /// Reads `bytes` as one integer, big-endian for `MsbFirst` and
+    /// little-endian for `LsbFirst`, so that it agrees with `from_uint`.
+    pub fn from_bytes(bytes: &[u8], order: BitOrder) -> Result<Self, OverflowError> {
+        let mut bits = Self::new(false);
+        let mut width = 0_usize;
+
+        for (j, byte) in bytes.iter().enumerate() {
+            let k = match order {
+                BitOrder::MsbFirst => bytes.len() - 1 - j,
+                BitOrder::LsbFirst => j,
+            };
+            let mut byte = *byte;
 
-            if i + 1 > N && bit {
-                return Err(OverflowError);
-            } else if bit {
-                bits[N - i - 1] = true;
+            while byte != 0 {
+                let p = 8 * k + byte.trailing_zeros() as usize;
+
+                if p < N {
+                    bits.set(order.index(N, p), true);
+                }
+
+                width = width.max(p + 1);
+                byte &= byte - 1;
             }
         }
 
+        if width > N {
+            return Err(OverflowError { width, capacity: N });
+        }
+
         Ok(bits)
     }
-}
 
-impl<const N: usize> TryFrom<u64> for Bits<N> {
-    type Error = OverflowError;
+    /// Inverse of `from_bytes`, using the fewest bytes that hold `N` bits.
+    pub fn to_bytes(self, order: BitOrder) -> Vec<u8> {
+        let len = N.div_ceil(8);
+        let mut bytes = vec![0_u8; len];
 
-    fn try_from(x: u64) -> Result<Bits<N>, Self::Error> {
-        let mut bits = Bits::<N>::new(false);
+        for i in self.ones() {
+            let p = order.index(N, i);
+            let j = match order {
+                BitOrder::MsbFirst => len - 1 - p / 8,
+                BitOrder::LsbFirst => p / 8,
+            };
 
-        for i in 0..64 {
-            let bit = (x >> i) & 0x01 == 0x01;
+            bytes[j] |= 1 << (p % 8);
+        }
 
-            if i + 1 > N && bit {
-                return Err(OverflowError);
-            } else if bit {
-                bits[N - i - 1] = true;
-            }
+        bytes
+    }
+}
+
+impl<const N: usize> From<[bool; N]> for Bits<N> {
+    fn from(values: [bool; N]) -> Self {
+        let mut bits = Self::new(false);
+
+        for (i, value) in values.into_iter().enumerate() {
+            bits.set(i, value);
         }
 
-        Ok(bits)
+        bits
     }
 }
 
-impl<const N: usize> TryFrom<u128> for Bits<N> {
-    type Error = OverflowError;
+impl<const N: usize> TryFrom<&[bool]> for Bits<N> {
+    type Error = LengthMismatchError;
 
-    fn try_from(x: u128) -> Result<Bits<N>, Self::Error> {
-        let mut bits = Bits::<N>::new(false);
+    fn try_from(values: &[bool]) -> Result<Bits<N>, Self::Error> {
+        let values: [bool; N] = values.try_into().map_err(|_| LengthMismatchError)?;
 
-        for i in 0..128 {
-            let bit = (x >> i) & 0x01 == 0x01;
+        Ok(Self::from(values))
+    }
+}
 
-            if i + 1 > N && bit {
-                return Err(OverflowError);
-            } else if bit {
-                bits[N - i - 1] = true;
-            }
-        }
+impl<const N: usize> TryFrom<&[u8]> for Bits<N> {
+    type Error = OverflowError;
 
-        Ok(bits)
+    fn try_from(bytes: &[u8]) -> Result<Bits<N>, Self::Error> {
+        Bits::from_bytes(bytes, BitOrder::MsbFirst)
     }
 }
@@ -0,0 +1,306 @@
+#![allow(dead_code)]
+
+use std::{collections::BTreeSet, fmt, str::FromStr};
+
+use crate::{
+    bits::{Bits, IncomparableError, ParseBitsError},
+    function::MonotoneFunction,
+    interval::{Interval, Points},
+    order::Colex,
+};
+
+/// Subcube in ternary notation, e.g. `1-0-`, where `-` leaves a coordinate
+/// free. The same set as an `Interval`, written the way covers are.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Cube<const N: usize> {
+    care: Bits<N>,
+    value: Bits<N>,
+}
+
+impl<const N: usize> Cube<N> {
+    /// Cube fixing the coordinates in `care` to their values in `value`.
+    pub fn new(care: &Bits<N>, value: &Bits<N>) -> Self {
+        Self {
+            care: *care,
+            value: *value & *care,
+        }
+    }
+
+    /// The cube with every coordinate free.
+    pub fn universe() -> Self {
+        Self::new(&Bits::new(false), &Bits::new(false))
+    }
+
+    /// The cube fixing every coordinate to `x`.
+    pub fn minterm(x: &Bits<N>) -> Self {
+        Self::new(&Bits::new(true), x)
+    }
+
+    pub fn care(&self) -> Bits<N> {
+        self.care
+    }
+
+    pub fn value(&self) -> Bits<N> {
+        self.value
+    }
+
+    /// `Some(value)` if coordinate `i` is fixed, `None` if it is free.
+    pub fn get(&self, i: usize) -> Option<bool> {
+        if self.care.get(i) {
+            Some(self.value.get(i))
+        } else {
+            None
+        }
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.care.count_zeroes()
+    }
+
+    pub fn contains(&self, x: &Bits<N>) -> bool {
+        (*x ^ self.value) & self.care == Bits::new(false)
+    }
+
+    pub fn contains_cube(&self, other: &Cube<N>) -> bool {
+        self.care <= other.care && other.value & self.care == self.value
+    }
+
+    /// Coordinates fixed to opposite values by the two cubes.
+    fn conflicts(&self, other: &Cube<N>) -> Bits<N> {
+        (self.value ^ other.value) & self.care & other.care
+    }
+
+    pub fn intersection(&self, other: &Cube<N>) -> Option<Cube<N>> {
+        if self.conflicts(other).or() {
+            return None;
+        }
+
+        Some(Self::new(
+            &(self.care | other.care),
+            &(self.value | other.value),
+        ))
+    }
+
+    /// Consensus of two cubes conflicting in exactly one coordinate: their
+    /// intersection once that coordinate is freed.
+    pub fn consensus(&self, other: &Cube<N>) -> Option<Cube<N>> {
+        let conflicts = self.conflicts(other);
+
+        if conflicts.count_ones() != 1 {
+            return None;
+        }
+
+        Some(Self::new(
+            &((self.care | other.care) & !conflicts),
+            &(self.value | other.value),
+        ))
+    }
+
+    /// Restriction to coordinate `i` taking `value`, with `i` then freed.
+    /// `None` if the cube requires the opposite value.
+    pub fn cofactor(&self, i: usize, value: bool) -> Option<Cube<N>> {
+        let mut literal = Self::universe();
+
+        literal.care.set(i, true);
+        literal.value.set(i, value);
+
+        self.cofactor_cube(&literal)
+    }
+
+    /// Restriction to `other`, with the coordinates `other` fixes then freed.
+    /// `None` if the cubes are disjoint.
+    pub fn cofactor_cube(&self, other: &Cube<N>) -> Option<Cube<N>> {
+        if self.conflicts(other).or() {
+            return None;
+        }
+
+        Some(Self::new(&(self.care & !other.care), &self.value))
+    }
+
+    pub fn iter(&self) -> Points<N> {
+        Interval::from(*self).iter()
+    }
+}
+
+impl<const N: usize> From<Interval<N>> for Cube<N> {
+    fn from(interval: Interval<N>) -> Self {
+        Self::new(&!interval.free(), &interval.lo())
+    }
+}
+
+impl<const N: usize> From<Cube<N>> for Interval<N> {
+    fn from(cube: Cube<N>) -> Self {
+        Interval::new(&cube.value, &(cube.value | !cube.care)).unwrap()
+    }
+}
+
+impl<const N: usize> TryFrom<(Bits<N>, Bits<N>)> for Cube<N> {
+    type Error = IncomparableError;
+
+    fn try_from((a, b): (Bits<N>, Bits<N>)) -> Result<Self, Self::Error> {
+        Ok(Self::from(Interval::new(&a, &b)?))
+    }
+}
+
+impl<const N: usize> FromStr for Cube<N> {
+    type Err = ParseBitsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != N {
+            return Err(ParseBitsError::LengthMismatch);
+        }
+
+        let mut cube = Self::universe();
+
+        for (i, c) in s.chars().enumerate() {
+            match c {
+                '0' => cube.care.set(i, true),
+                '1' => {
+                    cube.care.set(i, true);
+                    cube.value.set(i, true);
+                }
+                '-' => {}
+                _ => return Err(ParseBitsError::NonBinary),
+            }
+        }
+
+        Ok(cube)
+    }
+}
+
+impl<const N: usize> fmt::Display for Cube<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut out: String = "".to_string();
+
+        for i in 0..N {
+            out.push_str(match self.get(i) {
+                Some(true) => "1",
+                Some(false) => "0",
+                None => "-",
+            });
+        }
+
+        write!(f, "{}", out)
+    }
+}
+
+/// Union of cubes, i.e. a sum of products.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Cover<const N: usize> {
+    cubes: Vec<Cube<N>>,
+}
+
+impl<const N: usize> Cover<N> {
+    pub fn new(cubes: Vec<Cube<N>>) -> Self {
+        Self { cubes }
+    }
+
+    pub fn cubes(&self) -> &[Cube<N>] {
+        &self.cubes
+    }
+
+    pub fn push(&mut self, cube: Cube<N>) {
+        self.cubes.push(cube);
+    }
+
+    pub fn contains(&self, x: &Bits<N>) -> bool {
+        self.cubes.iter().any(|cube| cube.contains(x))
+    }
+
+    /// Every point covered, once each, in colex order.
+    pub fn minterms(&self) -> Vec<Bits<N>> {
+        let minterms: BTreeSet<Colex<Bits<N>>> = self
+            .cubes
+            .iter()
+            .flat_map(|cube| cube.iter().map(Colex))
+            .collect();
+
+        minterms.into_iter().map(|x| x.0).collect()
+    }
+
+    pub fn cofactor_cube(&self, cube: &Cube<N>) -> Cover<N> {
+        Self::new(
+            self.cubes
+                .iter()
+                .filter_map(|c| c.cofactor_cube(cube))
+                .collect(),
+        )
+    }
+
+    /// Whether the cubes cover the whole cube, by Shannon expansion on the
+    /// binate coordinate fixed most often. A cover that is unate in every
+    /// coordinate is a tautology only if it contains the universe.
+    pub fn is_tautology(&self) -> bool {
+        if self.cubes.iter().any(|cube| cube.care == Bits::new(false)) {
+            return true;
+        } else if self.cubes.is_empty() {
+            return false;
+        }
+
+        let mut counts = vec![(0_usize, 0_usize); N];
+
+        for cube in &self.cubes {
+            for i in cube.care.ones() {
+                if cube.value.get(i) {
+                    counts[i].1 += 1;
+                } else {
+                    counts[i].0 += 1;
+                }
+            }
+        }
+
+        let binate = (0..N)
+            .filter(|i| counts[*i].0 > 0 && counts[*i].1 > 0)
+            .max_by_key(|i| counts[*i].0 + counts[*i].1);
+
+        match binate {
+            Some(i) => [false, true].into_iter().all(|value| {
+                let mut literal = Cube::universe();
+
+                literal.care.set(i, true);
+                literal.value.set(i, value);
+
+                self.cofactor_cube(&literal).is_tautology()
+            }),
+            None => false,
+        }
+    }
+
+    /// Whether every point of `cube` is covered.
+    pub fn covers(&self, cube: &Cube<N>) -> bool {
+        self.cofactor_cube(cube).is_tautology()
+    }
+}
+
+impl<const N: usize> FromIterator<Cube<N>> for Cover<N> {
+    fn from_iter<I: IntoIterator<Item = Cube<N>>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+impl<const N: usize> fmt::Display for Cover<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cubes: Vec<String> = self.cubes.iter().map(|cube| cube.to_string()).collect();
+
+        write!(f, "{}", cubes.join(" + "))
+    }
+}
+
+impl<const N: usize> MonotoneFunction<N> {
+    /// Minimal implicants as cubes: `1` where the implicant is set, `-`
+    /// elsewhere.
+    pub fn cubes(&self) -> impl Iterator<Item = Cube<N>> + '_ {
+        self.implicants()
+            .map(|a| Cube::from(Interval::upper_shadow(&a)))
+    }
+
+    pub fn cover(&self) -> Cover<N> {
+        self.cubes().collect()
+    }
+}
+
+impl<const N: usize> fmt::Display for MonotoneFunction<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.cover(), f)
+    }
+}
@@ -0,0 +1,322 @@
+#![allow(dead_code)]
+
+use crate::{bits::Bits, family::Family, function::MonotoneFunction, minimize::minimize};
+
+/// Minimal transversals of `edges`, found by depth-first search over
+/// growing sets (Murakami and Uno's MMCS). Each branch adds a coordinate of
+/// the uncovered edge with the fewest candidates, and is cut as soon as some
+/// chosen coordinate stops being the only one hitting an edge, so every
+/// leaf is a distinct minimal transversal.
+pub fn transversals<const N: usize>(edges: &[Bits<N>]) -> Vec<Bits<N>> {
+    let mut search = Mmcs {
+        edges,
+        critical: vec![Vec::new(); N],
+        uncovered: (0..edges.len()).collect(),
+        found: Vec::new(),
+    };
+
+    search.run(Bits::new(false), Bits::new(true));
+    search.found
+}
+
+#[derive(Debug)]
+struct Mmcs<'a, const N: usize> {
+    edges: &'a [Bits<N>],
+    // Edges met by the current set in each chosen coordinate alone
+    critical: Vec<Vec<usize>>,
+    uncovered: Vec<usize>,
+    found: Vec<Bits<N>>,
+}
+
+impl<const N: usize> Mmcs<'_, N> {
+    fn run(&mut self, chosen: Bits<N>, mut candidates: Bits<N>) {
+        let edges = self.edges;
+        let Some(edge) = self
+            .uncovered
+            .iter()
+            .map(|e| edges[*e])
+            .min_by_key(|a| (*a & candidates).count_ones())
+        else {
+            self.found.push(chosen);
+            return;
+        };
+        let branches = edge & candidates;
+
+        candidates &= !branches;
+
+        for i in branches.ones() {
+            let (covered, uncovered) = self.uncovered.iter().partition(|e| edges[**e].get(i));
+            let shared: Vec<Vec<usize>> = chosen
+                .ones()
+                .map(|j| {
+                    let (shared, critical) =
+                        self.critical[j].iter().partition(|e| edges[**e].get(i));
+
+                    self.critical[j] = critical;
+                    shared
+                })
+                .collect();
+
+            self.critical[i] = covered;
+            self.uncovered = uncovered;
+
+            if chosen.ones().all(|j| !self.critical[j].is_empty()) {
+                let mut next = chosen;
+
+                next.set(i, true);
+                self.run(next, candidates);
+            }
+
+            for (j, shared) in chosen.ones().zip(shared) {
+                self.critical[j].extend(shared);
+            }
+
+            let covered = std::mem::take(&mut self.critical[i]);
+
+            self.uncovered.extend(covered);
+            candidates.set(i, true);
+        }
+    }
+}
+
+impl<const N: usize> MonotoneFunction<N> {
+    /// The dual `x ↦ ¬f(¬x)`, whose minimal implicants are the minimal
+    /// transversals of the minimal implicants of `f`.
+    pub fn dual(&self) -> MonotoneFunction<N> {
+        let implicants: Vec<Bits<N>> = self.implicants().collect();
+
+        MonotoneFunction {
+            implicants: transversals(&implicants).into(),
+        }
+    }
+
+    /// The dual by Berge's sequential algorithm, adding one implicant at a
+    /// time. Simpler than `dual` but with intermediate families that can be
+    /// far larger than the result.
+    pub fn dual_berge(&self) -> MonotoneFunction<N> {
+        MonotoneFunction {
+            implicants: self.family().blocker(),
+        }
+    }
+
+    /// Clauses of the minimal CNF, each standing for the disjunction of its
+    /// coordinates, so that `f` holds exactly where every clause meets `x`.
+    pub fn cnf(&self) -> Family<N> {
+        self.dual().implicants
+    }
+
+    /// Maximal points where `f` is false, the complements of the clauses.
+    pub fn maximal_false_points(&self) -> Family<N> {
+        self.cnf().complement()
+    }
+}
+
+/// Point `x` with `f(x) = g(¬x)`, showing that `g` is not the dual of `f`,
+/// or `None` if it is, by Fredman and Khachiyan's algorithm A, which
+/// splits on a most frequent coordinate.
+pub fn duality_witness_a<const N: usize>(
+    f: &MonotoneFunction<N>,
+    g: &MonotoneFunction<N>,
+) -> Option<Bits<N>> {
+    duality_witness(f, g, Split::A)
+}
+
+/// As `duality_witness_a` by algorithm B, which replaces one of the two
+/// halves of a split by a subproblem for each implicant containing the
+/// split coordinate when that coordinate is rare on one side, bringing the
+/// running time down to `m^o(log m)` in the total number of implicants.
+pub fn duality_witness_b<const N: usize>(
+    f: &MonotoneFunction<N>,
+    g: &MonotoneFunction<N>,
+) -> Option<Bits<N>> {
+    duality_witness(f, g, Split::B)
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Split {
+    A,
+    B,
+}
+
+fn duality_witness<const N: usize>(
+    f: &MonotoneFunction<N>,
+    g: &MonotoneFunction<N>,
+    split: Split,
+) -> Option<Bits<N>> {
+    // Implicants `a` of `f` and `b` of `g` that miss each other make `a` a
+    // point where both are true. Every subproblem inherits the property.
+    for a in f.implicants() {
+        if g.implicants().any(|b| !(a & b).or()) {
+            return Some(a);
+        }
+    }
+
+    let f: Vec<Bits<N>> = f.implicants().collect();
+    let g: Vec<Bits<N>> = g.implicants().collect();
+
+    witness(&f, &g, split)
+}
+
+/// Witness for the minimal implicants `f` and `g`, lightest first, which
+/// meet pairwise, so that any witness is a point where both are false.
+fn witness<const N: usize>(f: &[Bits<N>], g: &[Bits<N>], split: Split) -> Option<Bits<N>> {
+    let bottom = Bits::new(false);
+    let top = Bits::new(true);
+
+    // Constant functions, whose duals are the other constant. Otherwise
+    // neither side has the empty set, which would miss the other side.
+    match (f.first(), g.first()) {
+        (None, None) => return Some(top),
+        (None, Some(b)) => return (*b != bottom).then_some(top),
+        (Some(a), None) => return (*a != bottom).then_some(bottom),
+        (Some(_), Some(_)) => {}
+    }
+
+    if let Some(x) = sparse_witness(f, g) {
+        return Some(x);
+    }
+
+    let (i, f_frequency, g_frequency) = (0..N)
+        .map(|i| {
+            let count = |h: &[Bits<N>]| h.iter().filter(|a| a.get(i)).count();
+
+            (
+                i,
+                count(f) as f64 / f.len() as f64,
+                count(g) as f64 / g.len() as f64,
+            )
+        })
+        .max_by(|(_, f0, g0), (_, f1, g1)| f0.max(*g0).total_cmp(&f1.max(*g1)))
+        .unwrap();
+
+    if split == Split::B && g_frequency > f_frequency {
+        return witness(g, f, split).map(|x| !x);
+    }
+
+    // Implicants without `i`, and those with it with `i` cleared
+    let (f0, f1) = cofactors(f, i);
+    let (g0, g1) = cofactors(g, i);
+    let either = |h0: &[Bits<N>], h1: &[Bits<N>]| minimize([h0, h1].concat()).minimal;
+    let with = |mut x: Bits<N>, value| {
+        x.set(i, value);
+        x
+    };
+
+    // `f` with `i` cleared is `f0` and `g` with it set is `g0 ∨ g1`, and
+    // conversely `f0 ∨ f1` against `g0`
+    if let Some(x) = witness(&f0, &either(&g0, &g1), split) {
+        return Some(with(x, false));
+    }
+
+    let f01 = either(&f0, &f1);
+    let threshold = 1.0 / chi((f.len() * g.len()) as f64);
+
+    if split == Split::A || g_frequency >= threshold {
+        return witness(&f01, &g0, split).map(|x| with(x, true));
+    }
+
+    // With the first half dual, a point where `f0 ∨ f1` and `g0` are both
+    // false has `g0 ∨ g1` true at its complement, which so lies above an
+    // implicant `b` of `g1`. It is a witness for the two with the
+    // coordinates of `b` fixed, of which there are few as `i` is rare in `g`.
+    for b in &g1 {
+        let restricted_f: Vec<Bits<N>> = f01.iter().filter(|a| !(**a & *b).or()).copied().collect();
+        let restricted_g = minimize(g0.iter().map(|c| *c & !*b).collect()).minimal;
+
+        if let Some(x) = witness(&restricted_f, &restricted_g, split) {
+            return Some(with(x & !*b, true));
+        }
+    }
+
+    None
+}
+
+fn cofactors<const N: usize>(h: &[Bits<N>], i: usize) -> (Vec<Bits<N>>, Vec<Bits<N>>) {
+    let (with, without): (Vec<Bits<N>>, Vec<Bits<N>>) = h.iter().partition(|a| a.get(i));
+
+    let with = with
+        .into_iter()
+        .map(|mut a| {
+            a.set(i, false);
+            a
+        })
+        .collect();
+
+    (without, with)
+}
+
+/// A point where `f` and `g` at the complement are both false, found by
+/// fixing coordinates one at a time so as not to increase the expected
+/// number of true implicants at a uniform point, provided that expectation
+/// starts below one.
+fn sparse_witness<const N: usize>(f: &[Bits<N>], g: &[Bits<N>]) -> Option<Bits<N>> {
+    // Probability of each coordinate being set, a half until fixed
+    let mut p = [0.5; N];
+    let expectation = |p: &[f64; N]| -> f64 {
+        let f: f64 = f
+            .iter()
+            .map(|a| a.ones().map(|i| p[i]).product::<f64>())
+            .sum();
+        let g: f64 = g
+            .iter()
+            .map(|b| b.ones().map(|i| 1.0 - p[i]).product::<f64>())
+            .sum();
+
+        f + g
+    };
+
+    if expectation(&p) >= 1.0 {
+        return None;
+    }
+
+    let support = f.iter().chain(g).fold(Bits::new(false), |x, a| x | *a);
+
+    for i in support.ones() {
+        p[i] = 1.0;
+
+        let set = expectation(&p);
+
+        p[i] = 0.0;
+
+        if set < expectation(&p) {
+            p[i] = 1.0;
+        }
+    }
+
+    let mut x = Bits::new(false);
+
+    for i in support.ones().filter(|i| p[*i] == 1.0) {
+        x.set(i, true);
+    }
+
+    // Rounding might have let the expectation reach one
+    (!f.iter().any(|a| *a <= x) && !g.iter().any(|b| *b <= !x)).then_some(x)
+}
+
+/// Solution of `c^c = m`, the frequency bound of algorithm B.
+fn chi(m: f64) -> f64 {
+    let (mut lo, mut hi) = (1.0_f64, m.max(2.0));
+
+    for _ in 0..64 {
+        let c = (lo + hi) / 2.0;
+
+        if c * c.ln() < m.ln() {
+            lo = c;
+        } else {
+            hi = c;
+        }
+    }
+
+    hi
+}
+
+impl<const N: usize> MonotoneFunction<N> {
+    /// Point `x` with `self(x) = other(¬x)` if `other` is not the dual.
+    pub fn duality_witness(&self, other: &MonotoneFunction<N>) -> Option<Bits<N>> {
+        duality_witness_b(self, other)
+    }
+
+    pub fn is_dual_of(&self, other: &MonotoneFunction<N>) -> bool {
+        self.duality_witness(other).is_none()
+    }
+}
@@ -0,0 +1,447 @@
+#![allow(dead_code)]
+
+use rand::{thread_rng, Rng};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{
+        BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index, Not, Shl, ShlAssign,
+        Shr, ShrAssign,
+    },
+    str::FromStr,
+};
+
+use crate::{
+    bits::{Bits, IncomparableError, LengthMismatchError, ParseBitsError},
+    packed::{self, Packed},
+    util::rand_combination_with,
+};
+
+pub type DynZeroes = packed::Zeroes<DynBits>;
+pub type DynOnes = packed::Ones<DynBits>;
+pub type DynUpperShadow = packed::UpperShadow<DynBits>;
+pub type DynLowerShadow = packed::LowerShadow<DynBits>;
+pub type DynMidpoints = packed::Midpoints<DynBits>;
+pub type DynHorizon = packed::Horizon<DynBits>;
+pub type DynConverge = packed::Converge<DynBits>;
+pub type DynPaths = packed::Paths<DynBits>;
+
+/// Runtime-width counterpart of `Bits<N>`. Operators between values of
+/// different lengths panic; comparisons treat them as incomparable.
+#[derive(Clone)]
+pub struct DynBits {
+    len: usize,
+    limbs: Vec<u64>,
+}
+
+impl Packed for DynBits {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn limbs(&self) -> Cow<'_, [u64]> {
+        Cow::Borrowed(&self.limbs)
+    }
+
+    fn limbs_mut(&mut self) -> &mut [u64] {
+        &mut self.limbs
+    }
+}
+
+impl DynBits {
+    fn from_limbs(len: usize, mut limbs: Vec<u64>) -> Self {
+        limbs.resize(len.div_ceil(64), 0);
+        packed::trim(&mut limbs, len);
+
+        Self { len, limbs }
+    }
+
+    fn zip_with(&self, rhs: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        assert_eq!(self.len, rhs.len, "DynBits length mismatch");
+
+        let limbs = self
+            .limbs
+            .iter()
+            .zip(rhs.limbs.iter())
+            .map(|(a, b)| f(*a, *b))
+            .collect();
+
+        Self::from_limbs(self.len, limbs)
+    }
+
+    pub fn new(len: usize, value: bool) -> Self {
+        let limb = if value { u64::MAX } else { 0 };
+
+        Self::from_limbs(len, vec![limb; len.div_ceil(64)])
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(
+            i < self.len,
+            "index {} out of range for DynBits of length {}",
+            i,
+            self.len
+        );
+
+        packed::get(&self.limbs, i)
+    }
+
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(
+            i < self.len,
+            "index {} out of range for DynBits of length {}",
+            i,
+            self.len
+        );
+
+        packed::set(&mut self.limbs, i, value);
+    }
+
+    pub fn count_zeroes(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    pub fn count_ones(&self) -> usize {
+        packed::count_ones(&self.limbs)
+    }
+
+    pub fn zeroes(&self) -> DynZeroes {
+        packed::Zeroes::new(self)
+    }
+
+    pub fn ones(&self) -> DynOnes {
+        packed::Ones::new(self)
+    }
+
+    pub fn and(&self) -> bool {
+        self.count_ones() == self.len
+    }
+
+    pub fn or(&self) -> bool {
+        self.limbs.iter().any(|limb| *limb != 0)
+    }
+
+    pub fn distance(&self, other: &DynBits) -> Result<usize, IncomparableError> {
+        if self.partial_cmp(other).is_none() {
+            return Err(IncomparableError);
+        }
+
+        Ok((self ^ other).count_ones())
+    }
+
+    pub fn rand_midpoint(&self, other: &DynBits) -> Result<DynBits, IncomparableError> {
+        self.rand_midpoint_with(other, &mut thread_rng())
+    }
+
+    pub fn rand_midpoint_with(
+        &self,
+        other: &DynBits,
+        rng: &mut impl Rng,
+    ) -> Result<DynBits, IncomparableError> {
+        if self.partial_cmp(other).is_none() {
+            return Err(IncomparableError);
+        }
+
+        let origin = if *self > *other { other } else { self };
+        let diff = self ^ other;
+        let size = diff.count_ones();
+        // Account for cases where number of ones is not even
+        let count = if size.is_multiple_of(2) || rng.gen_bool(0.5) {
+            size / 2
+        } else {
+            (size / 2) + 1
+        };
+
+        let ones = Vec::from_iter(diff.ones());
+        let indices = rand_combination_with(&ones, count, rng);
+        let mut target = origin.clone();
+
+        for i in indices {
+            target.set(i, true);
+        }
+
+        Ok(target)
+    }
+
+    pub fn horizon(&self, lower: bool) -> DynHorizon {
+        packed::Horizon::new(self, lower)
+    }
+
+    pub fn midpoints(&self, other: &DynBits) -> Result<DynMidpoints, IncomparableError> {
+        packed::Midpoints::new(self, other)
+    }
+
+    pub fn converge(&self, other: &DynBits) -> Result<DynConverge, IncomparableError> {
+        packed::Converge::new(self, other)
+    }
+
+    pub fn converge_from(
+        &self,
+        other: &DynBits,
+        position: (usize, u128),
+    ) -> Result<DynConverge, IncomparableError> {
+        packed::Converge::resume(self, other, position)
+    }
+
+    pub fn paths(&self, other: &DynBits) -> Result<DynPaths, IncomparableError> {
+        packed::Paths::new(self, other)
+    }
+
+    pub fn lower_shadow(&self) -> DynLowerShadow {
+        packed::LowerShadow::new(self)
+    }
+
+    pub fn upper_shadow(&self) -> DynUpperShadow {
+        packed::UpperShadow::new(self)
+    }
+}
+
+impl PartialEq for DynBits {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.limbs == other.limbs
+    }
+}
+
+impl Eq for DynBits {}
+
+impl Hash for DynBits {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len.hash(state);
+        self.limbs.hash(state);
+    }
+}
+
+impl PartialOrd for DynBits {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.len != other.len {
+            return None;
+        } else if self == other {
+            return Some(Ordering::Equal);
+        } else if self.le(other) {
+            return Some(Ordering::Less);
+        } else if other.le(self) {
+            return Some(Ordering::Greater);
+        }
+
+        None
+    }
+
+    fn le(&self, other: &Self) -> bool {
+        self.len == other.len && packed::is_subset(&self.limbs, &other.limbs)
+    }
+}
+
+impl FromStr for DynBits {
+    type Err = ParseBitsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bits = Self::new(s.len(), false);
+
+        for (i, c) in s.chars().enumerate() {
+            if c == '0' {
+                bits.set(i, false);
+            } else if c == '1' {
+                bits.set(i, true);
+            } else {
+                return Err(ParseBitsError::NonBinary);
+            }
+        }
+
+        Ok(bits)
+    }
+}
+
+impl FromIterator<bool> for DynBits {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let values: Vec<bool> = iter.into_iter().collect();
+        let mut bits = Self::new(values.len(), false);
+
+        for (i, value) in values.into_iter().enumerate() {
+            bits.set(i, value);
+        }
+
+        bits
+    }
+}
+
+impl<const N: usize> From<Bits<N>> for DynBits {
+    fn from(bits: Bits<N>) -> Self {
+        Self::from_limbs(N, bits.limbs().to_vec())
+    }
+}
+
+impl<const N: usize> From<&Bits<N>> for DynBits {
+    fn from(bits: &Bits<N>) -> Self {
+        Self::from(*bits)
+    }
+}
+
+impl<const N: usize> TryFrom<&DynBits> for Bits<N> {
+    type Error = LengthMismatchError;
+
+    fn try_from(bits: &DynBits) -> Result<Bits<N>, Self::Error> {
+        if bits.len() != N {
+            return Err(LengthMismatchError);
+        }
+
+        Ok(Bits::from_limbs(&bits.limbs))
+    }
+}
+
+impl<const N: usize> TryFrom<DynBits> for Bits<N> {
+    type Error = LengthMismatchError;
+
+    fn try_from(bits: DynBits) -> Result<Bits<N>, Self::Error> {
+        Bits::try_from(&bits)
+    }
+}
+
+impl BitAnd for &DynBits {
+    type Output = DynBits;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a & b)
+    }
+}
+
+impl BitAnd for DynBits {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        &self & &rhs
+    }
+}
+
+impl BitAndAssign for DynBits {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = &*self & &rhs;
+    }
+}
+
+impl BitOr for &DynBits {
+    type Output = DynBits;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a | b)
+    }
+}
+
+impl BitOr for DynBits {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        &self | &rhs
+    }
+}
+
+impl BitOrAssign for DynBits {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = &*self | &rhs;
+    }
+}
+
+impl BitXor for &DynBits {
+    type Output = DynBits;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a ^ b)
+    }
+}
+
+impl BitXor for DynBits {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        &self ^ &rhs
+    }
+}
+
+impl BitXorAssign for DynBits {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        *self = &*self ^ &rhs;
+    }
+}
+
+impl Index<usize> for DynBits {
+    type Output = bool;
+
+    fn index(&self, i: usize) -> &Self::Output {
+        if self.get(i) {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+impl Not for DynBits {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        packed::complement(&self)
+    }
+}
+
+impl Shl<usize> for DynBits {
+    type Output = Self;
+
+    /// Moves every bit `rhs` places towards index 0.
+    fn shl(mut self, rhs: usize) -> Self::Output {
+        packed::shift_limbs_down(&mut self.limbs, rhs);
+
+        self
+    }
+}
+
+impl ShlAssign<usize> for DynBits {
+    fn shl_assign(&mut self, rhs: usize) {
+        packed::shift_limbs_down(&mut self.limbs, rhs);
+    }
+}
+
+impl Shr<usize> for DynBits {
+    type Output = Self;
+
+    /// Moves every bit `rhs` places away from index 0.
+    fn shr(mut self, rhs: usize) -> Self::Output {
+        packed::shift_limbs_up(&mut self.limbs, rhs);
+        packed::trim(&mut self.limbs, self.len);
+
+        self
+    }
+}
+
+impl ShrAssign<usize> for DynBits {
+    fn shr_assign(&mut self, rhs: usize) {
+        packed::shift_limbs_up(&mut self.limbs, rhs);
+        packed::trim(&mut self.limbs, self.len);
+    }
+}
+
+impl fmt::Debug for DynBits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for DynBits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut out: String = "".to_string();
+
+        for i in 0..self.len() {
+            out.push_str(if self.get(i) { "1" } else { "0" });
+        }
+
+        write!(f, "{}", out)
+    }
+}
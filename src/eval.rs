@@ -0,0 +1,161 @@
+#![allow(dead_code)]
+
+use crate::{bits::Bits, function::MonotoneFunction, packed::Packed};
+
+/// Monotone function prepared for fast evaluation. Each implicant is filed
+/// under its coordinate shared by the fewest implicants, so a point is only
+/// checked against implicants whose key it contains, and each bucket is
+/// sorted by weight so the scan stops at the first implicant heavier than
+/// the point. Implicants are stored as bare limbs, one after another.
+#[derive(Clone, Debug)]
+pub struct Compiled<const N: usize> {
+    // Whether the empty set is an implicant
+    always: bool,
+    keys: Bits<N>,
+    // Implicants of bucket `i` at `starts[i]..starts[i + 1]`
+    starts: Vec<usize>,
+    weights: Vec<usize>,
+    limbs: Vec<u64>,
+    // Coordinates of implicant `k` at `offsets[k]..offsets[k + 1]`, for
+    // bit-sliced evaluation
+    offsets: Vec<usize>,
+    coordinates: Vec<usize>,
+}
+
+impl<const N: usize> Compiled<N> {
+    const WORDS: usize = N.div_ceil(64);
+
+    pub fn new(f: &MonotoneFunction<N>) -> Self {
+        let mut frequency = [0; N];
+
+        for a in f.implicants() {
+            for i in a.ones() {
+                frequency[i] += 1;
+            }
+        }
+
+        // Graded order is lightest first, which sorting by key keeps
+        let mut keyed: Vec<(usize, Bits<N>)> = f
+            .implicants()
+            .filter_map(|a| a.ones().min_by_key(|i| frequency[*i]).map(|i| (i, a)))
+            .collect();
+
+        keyed.sort_by_key(|(i, _)| *i);
+
+        let mut compiled = Self {
+            always: f.implicants().next() == Some(Bits::new(false)),
+            keys: Bits::new(false),
+            starts: vec![0; N + 1],
+            weights: Vec::new(),
+            limbs: Vec::new(),
+            offsets: vec![0],
+            coordinates: Vec::new(),
+        };
+
+        for (i, a) in &keyed {
+            compiled.keys.set(*i, true);
+            compiled.starts[i + 1] += 1;
+            compiled.weights.push(a.count_ones());
+            compiled.limbs.extend(a.limbs().iter());
+            compiled.coordinates.extend(a.ones());
+            compiled.offsets.push(compiled.coordinates.len());
+        }
+
+        for i in 0..N {
+            compiled.starts[i + 1] += compiled.starts[i];
+        }
+
+        compiled
+    }
+
+    pub fn call(&self, x: Bits<N>) -> bool {
+        if self.always {
+            return true;
+        }
+
+        let x_limbs = x.limbs();
+        let weight = x.count_ones();
+
+        (self.keys & x).ones().any(|i| {
+            (self.starts[i]..self.starts[i + 1])
+                .take_while(|k| self.weights[*k] <= weight)
+                .any(|k| {
+                    let a = &self.limbs[k * Self::WORDS..(k + 1) * Self::WORDS];
+
+                    a.iter().zip(x_limbs.iter()).all(|(a, x)| a & !x == 0)
+                })
+        })
+    }
+
+    /// Values at 64 points given bit-sliced, word `i` holding coordinate `i`
+    /// of each point, as a word with bit `j` the value at point `j`. Each
+    /// bucket is tried only on the points not yet known true that contain
+    /// its key, and the scan stops once there are none.
+    pub fn call_sliced(&self, columns: &[u64; N]) -> u64 {
+        self.sliced(columns, u64::MAX)
+    }
+
+    /// As `call_sliced`, for the points in `lanes` only.
+    fn sliced(&self, columns: &[u64; N], lanes: u64) -> u64 {
+        if self.always {
+            return lanes;
+        }
+
+        let mut values = 0;
+
+        for i in self.keys.ones() {
+            let pending = lanes & !values & columns[i];
+
+            if pending == 0 {
+                continue;
+            }
+
+            for k in self.starts[i]..self.starts[i + 1] {
+                let word = self.coordinates[self.offsets[k]..self.offsets[k + 1]]
+                    .iter()
+                    .fold(pending, |word, j| word & columns[*j]);
+
+                values |= word;
+            }
+
+            if values == lanes {
+                break;
+            }
+        }
+
+        values
+    }
+
+    /// Values at each of `xs`, evaluated 64 at a time by `call_sliced`.
+    pub fn call_batch(&self, xs: &[Bits<N>]) -> Vec<bool> {
+        let mut values = Vec::with_capacity(xs.len());
+
+        for chunk in xs.chunks(64) {
+            let mut columns = [0; N];
+
+            for (j, x) in chunk.iter().enumerate() {
+                for i in x.ones() {
+                    columns[i] |= 1 << j;
+                }
+            }
+
+            let word = self.sliced(&columns, u64::MAX >> (64 - chunk.len()));
+
+            values.extend((0..chunk.len()).map(|j| word >> j & 1 == 1));
+        }
+
+        values
+    }
+}
+
+impl<const N: usize> MonotoneFunction<N> {
+    pub fn compile(&self) -> Compiled<N> {
+        Compiled::new(self)
+    }
+}
+
+impl<const N: usize> From<&MonotoneFunction<N>> for Compiled<N> {
+    fn from(f: &MonotoneFunction<N>) -> Self {
+        Self::new(f)
+    }
+}
@@ -0,0 +1,217 @@
+#![allow(dead_code)]
+
+use std::collections::BTreeSet;
+
+use crate::{
+    bits::{Bits, Converge},
+    minimize::minimize,
+    order::Graded,
+};
+
+/// Set system over `0..N`: a set of points of the cube, kept in graded
+/// order.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Family<const N: usize> {
+    members: BTreeSet<Graded<Bits<N>>>,
+}
+
+impl<const N: usize> Family<N> {
+    pub fn new() -> Self {
+        Self {
+            members: BTreeSet::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn contains(&self, x: &Bits<N>) -> bool {
+        self.members.contains(&Graded(*x))
+    }
+
+    /// Adds `x`, returning whether it was new.
+    pub fn insert(&mut self, x: Bits<N>) -> bool {
+        self.members.insert(Graded(x))
+    }
+
+    /// Removes `x`, returning whether it was a member.
+    pub fn remove(&mut self, x: &Bits<N>) -> bool {
+        self.members.remove(&Graded(*x))
+    }
+
+    /// Members in graded order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Bits<N>> + '_ {
+        self.members.iter().map(|x| x.0)
+    }
+
+    /// Whether some member lies at or below `x`.
+    pub fn is_below(&self, x: &Bits<N>) -> bool {
+        self.iter().any(|a| a <= *x)
+    }
+
+    /// Whether some member lies at or above `x`.
+    pub fn is_above(&self, x: &Bits<N>) -> bool {
+        self.iter().any(|a| *x <= a)
+    }
+
+    /// Members with no other member below them.
+    pub fn minimal(&self) -> Family<N> {
+        minimize(self.iter().collect()).minimal.into()
+    }
+
+    /// Members with no other member above them, the complements of the
+    /// minimal complements.
+    pub fn maximal(&self) -> Family<N> {
+        self.complement().minimal().complement()
+    }
+
+    pub fn is_antichain(&self) -> bool {
+        self.minimal().len() == self.len()
+    }
+
+    /// Every point above some member, generated on demand.
+    pub fn up_closure(&self) -> Closure<N> {
+        Closure::new(self.minimal().iter().collect(), true)
+    }
+
+    /// Every point below some member, generated on demand.
+    pub fn down_closure(&self) -> Closure<N> {
+        Closure::new(self.maximal().iter().rev().collect(), false)
+    }
+
+    pub fn union(&self, other: &Family<N>) -> Family<N> {
+        Self {
+            members: &self.members | &other.members,
+        }
+    }
+
+    pub fn intersection(&self, other: &Family<N>) -> Family<N> {
+        Self {
+            members: &self.members & &other.members,
+        }
+    }
+
+    pub fn difference(&self, other: &Family<N>) -> Family<N> {
+        Self {
+            members: &self.members - &other.members,
+        }
+    }
+
+    /// Complements of the members.
+    pub fn complement(&self) -> Family<N> {
+        self.iter().map(|x| !x).collect()
+    }
+
+    /// Minimal sets meeting every member, i.e. the minimal transversals,
+    /// found by adding the members one at a time (Berge). The blocker of the
+    /// empty family is the empty set alone, and a family with the empty set
+    /// as a member has none.
+    pub fn blocker(&self) -> Family<N> {
+        let mut transversals: Family<N> = [Bits::new(false)].into_iter().collect();
+
+        for a in self.minimal().iter() {
+            let mut next = Family::new();
+
+            for t in transversals.iter() {
+                if (t & a).or() {
+                    next.insert(t);
+                } else {
+                    for i in a.ones() {
+                        let mut u = t;
+
+                        u.set(i, true);
+                        next.insert(u);
+                    }
+                }
+            }
+
+            transversals = next.minimal();
+        }
+
+        transversals
+    }
+}
+
+impl<const N: usize> FromIterator<Bits<N>> for Family<N> {
+    fn from_iter<I: IntoIterator<Item = Bits<N>>>(iter: I) -> Self {
+        Self {
+            members: iter.into_iter().map(Graded).collect(),
+        }
+    }
+}
+
+impl<const N: usize> From<Vec<Bits<N>>> for Family<N> {
+    fn from(members: Vec<Bits<N>>) -> Self {
+        members.into_iter().collect()
+    }
+}
+
+impl<const N: usize> Extend<Bits<N>> for Family<N> {
+    fn extend<I: IntoIterator<Item = Bits<N>>>(&mut self, iter: I) {
+        self.members.extend(iter.into_iter().map(Graded));
+    }
+}
+
+/// Up-closure or down-closure of an antichain of generators, as the union of
+/// their shadows. A point is yielded from the first generator it lies
+/// beyond, so nothing but the generators is remembered.
+#[derive(Clone, Debug)]
+pub struct Closure<const N: usize> {
+    generators: Vec<Bits<N>>,
+    index: usize,
+    walk: Option<Converge<N>>,
+    up: bool,
+}
+
+impl<const N: usize> Closure<N> {
+    fn new(generators: Vec<Bits<N>>, up: bool) -> Self {
+        Self {
+            generators,
+            index: 0,
+            walk: None,
+            up,
+        }
+    }
+
+    fn beyond(&self, a: &Bits<N>, x: &Bits<N>) -> bool {
+        if self.up {
+            a <= x
+        } else {
+            x <= a
+        }
+    }
+}
+
+impl<const N: usize> Iterator for Closure<N> {
+    type Item = Bits<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.walk.is_none() {
+                let a = self.generators.get(self.index)?;
+                let end = Bits::new(self.up);
+
+                self.walk = Some(a.converge(&end).unwrap());
+            }
+
+            match self.walk.as_mut().unwrap().next() {
+                Some((_, x)) => {
+                    let earlier = &self.generators[..self.index];
+
+                    if !earlier.iter().any(|a| self.beyond(a, &x)) {
+                        return Some(x);
+                    }
+                }
+                None => {
+                    self.walk = None;
+                    self.index += 1;
+                }
+            }
+        }
+    }
+}
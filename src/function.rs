@@ -0,0 +1,214 @@
+#![allow(dead_code)]
+
+use std::{
+    collections::BTreeSet,
+    ops::{BitAnd, BitOr},
+};
+
+use crate::{
+    bits::{Bits, LengthMismatchError},
+    dynbits::DynBits,
+    family::Family,
+    minimize::minimize,
+    order::Graded,
+};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MonotoneFunction<const N: usize> {
+    pub(crate) implicants: Family<N>,
+}
+
+impl<const N: usize> MonotoneFunction<N> {
+    pub fn new(implicants: Vec<Bits<N>>) -> Self {
+        Self {
+            implicants: Family::from(minimize(implicants).minimal),
+        }
+    }
+
+    /// Minimal implicants in graded order.
+    pub fn implicants(&self) -> impl Iterator<Item = Bits<N>> + '_ {
+        self.implicants.iter()
+    }
+
+    /// Minimal implicants as a family.
+    pub fn family(&self) -> &Family<N> {
+        &self.implicants
+    }
+
+    pub fn call(&self, x: Bits<N>) -> bool {
+        self.implicants.is_below(&x)
+    }
+
+    /// The constant function, with no implicants or the empty set alone.
+    pub fn constant(value: bool) -> Self {
+        let implicants = if value {
+            vec![Bits::new(false)]
+        } else {
+            vec![]
+        };
+
+        Self::new(implicants)
+    }
+
+    /// The projection onto coordinate `i`.
+    pub fn variable(i: usize) -> Self {
+        let mut x = Bits::new(false);
+
+        x.set(i, true);
+        Self::new(vec![x])
+    }
+
+    /// Conjunction, whose implicants are the unions of one from each side.
+    pub fn and(&self, other: &MonotoneFunction<N>) -> MonotoneFunction<N> {
+        Self::new(
+            self.implicants()
+                .flat_map(|a| other.implicants().map(move |b| a | b))
+                .collect(),
+        )
+    }
+
+    /// Disjunction, whose implicants are those of either side.
+    pub fn or(&self, other: &MonotoneFunction<N>) -> MonotoneFunction<N> {
+        Self::new(self.implicants().chain(other.implicants()).collect())
+    }
+
+    /// Function true where at least `k` of `functions` are, built up one
+    /// function at a time with the thresholds for every count below `k`.
+    pub fn threshold(functions: &[MonotoneFunction<N>], k: usize) -> MonotoneFunction<N> {
+        // At least `j` of the functions so far, for each `j` up to `k`
+        let mut at_least: Vec<MonotoneFunction<N>> =
+            (0..=k).map(|j| Self::constant(j == 0)).collect();
+
+        for f in functions {
+            for j in (1..=k).rev() {
+                at_least[j] = at_least[j].or(&f.and(&at_least[j - 1]));
+            }
+        }
+
+        at_least.swap_remove(k)
+    }
+
+    /// `f` with coordinate `i` replaced by `g`, i.e. `f0 ∨ (g ∧ f1)` for the
+    /// implicants `f0` without `i` and `f1` with it, `i` removed.
+    pub fn substitute(&self, i: usize, g: &MonotoneFunction<N>) -> MonotoneFunction<N> {
+        let (with, without): (Vec<Bits<N>>, Vec<Bits<N>>) =
+            self.implicants().partition(|a| a.get(i));
+        let with = Self::new(
+            with.into_iter()
+                .map(|mut a| {
+                    a.set(i, false);
+                    a
+                })
+                .collect(),
+        );
+
+        Self::new(without).or(&with.and(g))
+    }
+
+    /// `f(g_0(x), …, g_{N-1}(x))`, the disjunction over the implicants of
+    /// `f` of the conjunction of the inner functions they select.
+    pub fn compose<const M: usize>(&self, inner: &[MonotoneFunction<M>; N]) -> MonotoneFunction<M> {
+        self.implicants()
+            .map(|a| {
+                a.ones()
+                    .fold(MonotoneFunction::constant(true), |h, i| h.and(&inner[i]))
+            })
+            .fold(MonotoneFunction::constant(false), |h, term| h.or(&term))
+    }
+}
+
+impl<const N: usize> BitAnd for &MonotoneFunction<N> {
+    type Output = MonotoneFunction<N>;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.and(rhs)
+    }
+}
+
+impl<const N: usize> BitAnd for MonotoneFunction<N> {
+    type Output = MonotoneFunction<N>;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.and(&rhs)
+    }
+}
+
+impl<const N: usize> BitOr for &MonotoneFunction<N> {
+    type Output = MonotoneFunction<N>;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.or(rhs)
+    }
+}
+
+impl<const N: usize> BitOr for MonotoneFunction<N> {
+    type Output = MonotoneFunction<N>;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.or(&rhs)
+    }
+}
+
+/// Runtime-width counterpart of `MonotoneFunction<N>`.
+#[derive(Clone, Debug)]
+pub struct DynMonotoneFunction {
+    len: usize,
+    pub(crate) implicants: BTreeSet<Graded<DynBits>>,
+}
+
+impl DynMonotoneFunction {
+    /// Fails if the implicants do not all have length `len`.
+    pub fn new(len: usize, implicants: Vec<DynBits>) -> Result<Self, LengthMismatchError> {
+        if implicants.iter().any(|a| a.len() != len) {
+            return Err(LengthMismatchError);
+        }
+
+        Ok(Self {
+            len,
+            implicants: minimize(implicants)
+                .minimal
+                .into_iter()
+                .map(Graded)
+                .collect(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Minimal implicants in graded order.
+    pub fn implicants(&self) -> impl Iterator<Item = &DynBits> + '_ {
+        self.implicants.iter().map(|a| &a.0)
+    }
+
+    pub fn call(&self, x: &DynBits) -> bool {
+        self.implicants().any(|a| &(a & x) == a)
+    }
+}
+
+impl<const N: usize> From<MonotoneFunction<N>> for DynMonotoneFunction {
+    fn from(f: MonotoneFunction<N>) -> Self {
+        Self {
+            len: N,
+            implicants: f.implicants().map(|a| Graded(DynBits::from(a))).collect(),
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<DynMonotoneFunction> for MonotoneFunction<N> {
+    type Error = LengthMismatchError;
+
+    fn try_from(f: DynMonotoneFunction) -> Result<Self, Self::Error> {
+        if f.len != N {
+            return Err(LengthMismatchError);
+        }
+
+        let implicants = f
+            .implicants()
+            .map(Bits::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { implicants })
+    }
+}
@@ -0,0 +1,277 @@
+#![allow(dead_code)]
+
+use std::iter::FusedIterator;
+
+use crate::{
+    bits::{Bits, IncomparableError},
+    dynbits::DynBits,
+    packed::{self, span, Packed},
+    util::binomial,
+};
+
+/// Reflected Gray code over an interval, starting at its bottom. Each item
+/// carries the index flipped to reach it, `None` for the first. Steps
+/// alternate between flipping the first free coordinate and the one after
+/// the first set free coordinate, so no counter limits the dimension.
+#[derive(Clone, Debug)]
+pub struct GrayCode<B> {
+    cursor: B,
+    free: Vec<usize>,
+    // Whether an even number of free coordinates is set
+    even: bool,
+    started: bool,
+    done: bool,
+    // `None` once 2^d overflows
+    remaining: Option<u128>,
+}
+
+impl<B: Packed> GrayCode<B> {
+    pub(crate) fn new(start: &B, end: &B) -> Result<Self, IncomparableError> {
+        let (cursor, free, _) = span(start, end)?;
+
+        Ok(Self {
+            cursor,
+            remaining: 1_u128.checked_shl(free.len() as u32),
+            free,
+            even: true,
+            started: false,
+            done: false,
+        })
+    }
+
+    /// Number of points, including those already yielded, or `None` if that
+    /// overflows a `u128`.
+    pub fn total(&self) -> Option<u128> {
+        1_u128.checked_shl(self.free.len() as u32)
+    }
+
+    fn take(&mut self) {
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+    }
+}
+
+impl<B: Packed> Iterator for GrayCode<B> {
+    type Item = (B, Option<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        } else if !self.started {
+            self.started = true;
+            self.take();
+
+            return Some((self.cursor.clone(), None));
+        }
+
+        let j = if self.even {
+            0
+        } else {
+            let limbs = self.cursor.limbs();
+
+            self.free
+                .iter()
+                .position(|i| packed::get(&limbs, *i))
+                .unwrap()
+                + 1
+        };
+
+        // The walk ends at the top free coordinate alone
+        if j == self.free.len() {
+            self.done = true;
+
+            return None;
+        }
+
+        let i = self.free[j];
+        let value = packed::get(&self.cursor.limbs(), i);
+
+        packed::set(self.cursor.limbs_mut(), i, !value);
+        self.even = !self.even;
+        self.take();
+
+        Some((self.cursor.clone(), Some(i)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining.map(usize::try_from) {
+            _ if self.done => (0, Some(0)),
+            Some(Ok(remaining)) => (remaining, Some(remaining)),
+            _ => (usize::MAX, None),
+        }
+    }
+}
+
+impl<B: Packed> FusedIterator for GrayCode<B> {}
+
+/// Revolving-door order (Knuth, TAOCP 7.2.1.3, Algorithm R) of one layer of
+/// an interval. Each item carries the `(cleared, set)` pair of indices that
+/// moved to reach it, `None` for the first.
+#[derive(Clone, Debug)]
+pub struct RevolvingDoor<const N: usize> {
+    origin: Bits<N>,
+    free: Vec<usize>,
+    // c[1..=k] are the chosen positions in `free`, c[k + 1] a sentinel
+    c: Vec<usize>,
+    k: usize,
+    previous: Option<Bits<N>>,
+    remaining: u128,
+}
+
+impl<const N: usize> RevolvingDoor<N> {
+    fn new(start: &Bits<N>, end: &Bits<N>, k: usize) -> Result<Self, IncomparableError> {
+        let (origin, free, _) = span(start, end)?;
+        let mut c = vec![0; k + 2];
+
+        for (j, cj) in c.iter_mut().enumerate().take(k + 1).skip(1) {
+            *cj = j - 1;
+        }
+
+        c[k + 1] = free.len();
+
+        Ok(Self {
+            origin,
+            remaining: binomial(free.len(), k),
+            free,
+            c,
+            k,
+            previous: None,
+        })
+    }
+
+    /// Number of points, including those already yielded.
+    pub fn total(&self) -> u128 {
+        binomial(self.free.len(), self.k)
+    }
+
+    fn point(&self) -> Bits<N> {
+        let mut point = self.origin;
+
+        for j in 1..=self.k {
+            point.set(self.free[self.c[j]], true);
+        }
+
+        point
+    }
+
+    /// Advances `c` to the next combination, following steps R3 to R5.
+    fn advance(&mut self) {
+        let (c, k) = (&mut self.c, self.k);
+
+        if k % 2 == 1 {
+            if c[1] + 1 < c[2] {
+                c[1] += 1;
+
+                return;
+            }
+        } else if c[1] > 0 {
+            c[1] -= 1;
+
+            return;
+        }
+
+        let mut j = 2;
+        let mut decrease = k % 2 == 1;
+
+        while j <= k {
+            if decrease {
+                // R4, where c[j] == c[j - 1] + 1
+                if c[j] >= j {
+                    c[j] = c[j - 1];
+                    c[j - 1] = j - 2;
+
+                    return;
+                }
+            } else {
+                // R5, where c[j - 1] == j - 2
+                if c[j] + 1 < c[j + 1] {
+                    c[j - 1] = c[j];
+                    c[j] += 1;
+
+                    return;
+                }
+            }
+
+            decrease = !decrease;
+            j += 1;
+        }
+    }
+}
+
+impl<const N: usize> Iterator for RevolvingDoor<N> {
+    type Item = (Bits<N>, Option<(usize, usize)>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+
+        let previous = match self.previous {
+            Some(previous) => {
+                self.advance();
+                previous
+            }
+            None => {
+                let point = self.point();
+                self.previous = Some(point);
+
+                return Some((point, None));
+            }
+        };
+
+        let point = self.point();
+        let cleared = (previous & !point).ones().next().unwrap();
+        let set = (point & !previous).ones().next().unwrap();
+
+        self.previous = Some(point);
+
+        Some((point, Some((cleared, set))))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl<const N: usize> Bits<N> {
+    /// Reflected Gray code over the whole cube.
+    pub fn gray_code() -> GrayCode<Bits<N>> {
+        GrayCode::new(&Self::new(false), &Self::new(true)).unwrap()
+    }
+
+    /// Reflected Gray code over the interval spanned by `self` and `other`.
+    pub fn interval_gray_code(
+        &self,
+        other: &Bits<N>,
+    ) -> Result<GrayCode<Bits<N>>, IncomparableError> {
+        GrayCode::new(self, other)
+    }
+
+    /// Revolving-door order of the points of weight `k`.
+    pub fn revolving_door(k: usize) -> RevolvingDoor<N> {
+        RevolvingDoor::new(&Self::new(false), &Self::new(true), k).unwrap()
+    }
+
+    /// Revolving-door order of the points `k` steps above the bottom of the
+    /// interval spanned by `self` and `other`.
+    pub fn interval_revolving_door(
+        &self,
+        other: &Bits<N>,
+        k: usize,
+    ) -> Result<RevolvingDoor<N>, IncomparableError> {
+        RevolvingDoor::new(self, other, k)
+    }
+}
+
+impl DynBits {
+    /// Reflected Gray code over the whole cube of dimension `len`.
+    pub fn gray_code(len: usize) -> GrayCode<DynBits> {
+        GrayCode::new(&Self::new(len, false), &Self::new(len, true)).unwrap()
+    }
+}
@@ -0,0 +1,254 @@
+#![allow(dead_code)]
+
+use rand::{thread_rng, Rng};
+use std::fmt;
+
+use crate::{
+    bits::{Bits, Converge, IncomparableError, Midpoints, Paths, SymmetricChains},
+    packed::{self, Packed},
+    rank::Layer,
+};
+
+/// Interval `[lo, hi]` of the Boolean lattice, i.e. every point `x` with
+/// `lo <= x <= hi`. Equivalently a subcube whose free coordinates are those
+/// where `lo` and `hi` differ.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Interval<const N: usize> {
+    lo: Bits<N>,
+    hi: Bits<N>,
+}
+
+impl<const N: usize> Interval<N> {
+    /// Interval spanned by two comparable points, given in either order.
+    pub fn new(a: &Bits<N>, b: &Bits<N>) -> Result<Self, IncomparableError> {
+        if a <= b {
+            Ok(Self { lo: *a, hi: *b })
+        } else if b <= a {
+            Ok(Self { lo: *b, hi: *a })
+        } else {
+            Err(IncomparableError)
+        }
+    }
+
+    pub fn cube() -> Self {
+        Self {
+            lo: Bits::new(false),
+            hi: Bits::new(true),
+        }
+    }
+
+    /// Every point above `x`, as enumerated by `Bits::upper_shadow`.
+    pub fn upper_shadow(x: &Bits<N>) -> Self {
+        Self {
+            lo: *x,
+            hi: Bits::new(true),
+        }
+    }
+
+    /// Every point below `x`, as enumerated by `Bits::lower_shadow`.
+    pub fn lower_shadow(x: &Bits<N>) -> Self {
+        Self {
+            lo: Bits::new(false),
+            hi: *x,
+        }
+    }
+
+    pub fn lo(&self) -> Bits<N> {
+        self.lo
+    }
+
+    pub fn hi(&self) -> Bits<N> {
+        self.hi
+    }
+
+    /// Coordinates that vary within the interval.
+    pub fn free(&self) -> Bits<N> {
+        self.lo ^ self.hi
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.free().count_ones()
+    }
+
+    /// Number of points, `2^dimension`, or `None` for dimension 128 and up
+    /// where that overflows a `u128`.
+    pub fn size(&self) -> Option<u128> {
+        1_u128.checked_shl(self.dimension() as u32)
+    }
+
+    pub fn contains(&self, x: &Bits<N>) -> bool {
+        self.lo <= *x && *x <= self.hi
+    }
+
+    pub fn is_subinterval(&self, other: &Interval<N>) -> bool {
+        other.lo <= self.lo && self.hi <= other.hi
+    }
+
+    pub fn intersection(&self, other: &Interval<N>) -> Option<Interval<N>> {
+        let lo = self.lo | other.lo;
+        let hi = self.hi & other.hi;
+
+        if lo <= hi {
+            Some(Self { lo, hi })
+        } else {
+            None
+        }
+    }
+
+    /// Smallest interval containing both.
+    pub fn hull(&self, other: &Interval<N>) -> Interval<N> {
+        Self {
+            lo: self.lo & other.lo,
+            hi: self.hi | other.hi,
+        }
+    }
+
+    /// Splits `self` minus `other` into pairwise disjoint intervals, at most
+    /// one per free coordinate of `self` that `other` pins down.
+    pub fn difference(&self, other: &Interval<N>) -> Vec<Interval<N>> {
+        let common = match self.intersection(other) {
+            Some(common) => common,
+            None => return vec![*self],
+        };
+        let mut rest = *self;
+        let mut pieces = Vec::new();
+
+        for i in (self.free() & !common.free()).ones() {
+            let value = common.lo.get(i);
+            let mut piece = rest;
+
+            piece.lo.set(i, !value);
+            piece.hi.set(i, !value);
+            pieces.push(piece);
+
+            rest.lo.set(i, value);
+            rest.hi.set(i, value);
+        }
+
+        pieces
+    }
+
+    pub fn iter(&self) -> Points<N> {
+        Points::new(self)
+    }
+
+    pub fn distance(&self) -> usize {
+        self.dimension()
+    }
+
+    pub fn rand_midpoint(&self) -> Bits<N> {
+        self.rand_midpoint_with(&mut thread_rng())
+    }
+
+    pub fn rand_midpoint_with(&self, rng: &mut impl Rng) -> Bits<N> {
+        Bits::rand_midpoint_between(&self.lo, &self.hi, rng)
+    }
+
+    pub fn midpoints(&self) -> Midpoints<N> {
+        packed::Midpoints::between(&self.lo, &self.hi)
+    }
+
+    /// Points `k` steps above `lo`.
+    pub fn layer(&self, k: usize) -> Layer<N> {
+        packed::Layer::between(&self.lo, &self.hi, k)
+    }
+
+    /// Walks upwards from `lo`.
+    pub fn converge(&self) -> Converge<N> {
+        packed::Converge::between(&self.lo, &self.hi)
+    }
+
+    pub fn paths(&self) -> Paths<N> {
+        packed::Paths::between(&self.lo, &self.hi)
+    }
+
+    pub fn symmetric_chains(&self) -> SymmetricChains<N> {
+        SymmetricChains::new(self)
+    }
+}
+
+impl<const N: usize> fmt::Display for Interval<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+impl<const N: usize> IntoIterator for Interval<N> {
+    type Item = Bits<N>;
+    type IntoIter = Points<N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Points::new(&self)
+    }
+}
+
+impl<const N: usize> IntoIterator for &Interval<N> {
+    type Item = Bits<N>;
+    type IntoIter = Points<N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Points::new(self)
+    }
+}
+
+impl<const N: usize> Bits<N> {
+    pub fn interval(&self, other: &Bits<N>) -> Result<Interval<N>, IncomparableError> {
+        Interval::new(self, other)
+    }
+}
+
+/// Points of an interval in colex order of their free coordinates.
+#[derive(Clone, Debug)]
+pub struct Points<const N: usize> {
+    cursor: Option<Bits<N>>,
+    free: Bits<N>,
+    remaining: Option<u128>,
+}
+
+impl<const N: usize> Points<N> {
+    fn new(interval: &Interval<N>) -> Self {
+        Self {
+            cursor: Some(interval.lo),
+            free: interval.free(),
+            remaining: interval.size(),
+        }
+    }
+
+    /// Number of points, including those already yielded, or `None` if that
+    /// overflows a `u128`.
+    pub fn total(&self) -> Option<u128> {
+        1_u128.checked_shl(self.free.count_ones() as u32)
+    }
+}
+
+impl<const N: usize> Iterator for Points<N> {
+    type Item = Bits<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.cursor?;
+        let mut next = current;
+        let mut carry = true;
+
+        // Add one to the free coordinates, treating the fixed ones as if
+        // they were already set so that the carry passes over them
+        for (limb, free) in next.limbs_mut().iter_mut().zip(self.free.limbs().iter()) {
+            let (sum, overflow) = (*limb | !free).overflowing_add(carry as u64);
+
+            *limb = (*limb & !free) | (sum & free);
+            carry = overflow;
+        }
+
+        // Carrying out of the last limb means every point has been seen
+        self.remaining = self.remaining.map(|r| r - 1);
+        self.cursor = if carry { None } else { Some(next) };
+
+        Some(current)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining.map(usize::try_from) {
+            Some(Ok(remaining)) => (remaining, Some(remaining)),
+            _ => (usize::MAX, None),
+        }
+    }
+}
@@ -0,0 +1,247 @@
+#![allow(dead_code)]
+
+use petgraph::{graph::NodeIndex, Graph, Undirected};
+use rand::{rngs::StdRng, SeedableRng};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    bits::Bits,
+    dynbits::DynBits,
+    family::Family,
+    function::{DynMonotoneFunction, MonotoneFunction},
+    order::Graded,
+};
+
+/// Settings shared by `Learner` and `DynLearner`.
+#[derive(Clone, Debug, Default)]
+pub struct LearnerConfig {
+    /// Seed for every random choice the learner makes. Runs with the same
+    /// seed and oracle are identical; `None` seeds from the OS.
+    pub seed: Option<u64>,
+}
+
+impl LearnerConfig {
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        }
+    }
+}
+
+pub struct Learner<const N: usize> {
+    oracle: MonotoneFunction<N>,
+    lower_frontier: Family<N>,
+    upper_frontier: Family<N>,
+    iterations: usize,
+    rng: StdRng,
+}
+
+impl<const N: usize> Learner<N> {
+    pub fn new(oracle: MonotoneFunction<N>) -> Self {
+        Self::with_config(oracle, LearnerConfig::default())
+    }
+
+    pub fn with_config(oracle: MonotoneFunction<N>, config: LearnerConfig) -> Self {
+        Self {
+            oracle,
+            lower_frontier: Family::new(),
+            upper_frontier: Family::new(),
+            iterations: 0,
+            rng: config.rng(),
+        }
+    }
+
+    pub fn iterate(&mut self) {
+        if self.iterations == 0 {
+            assert!(self.lower_frontier.is_empty());
+            assert!(self.upper_frontier.is_empty());
+
+            let eet = Bits::<N>::new(false);
+            let tee = Bits::<N>::new(true);
+            let x = eet.rand_midpoint_with(&tee, &mut self.rng).unwrap();
+
+            if self.oracle.call(x) {
+                self.upper_frontier.insert(x);
+            } else {
+                self.lower_frontier.insert(x);
+            }
+        }
+
+        self.iterations += 1;
+    }
+
+    /// Least function agreeing with every true point found so far.
+    pub fn hypothesis(&self) -> MonotoneFunction<N> {
+        MonotoneFunction::new(self.upper_frontier.iter().collect())
+    }
+
+    /// Point lying neither above a known true point nor below a known false
+    /// one, if any. Such points are the witnesses that the hypothesis is not
+    /// dual to the function generated by the complements of the lower
+    /// frontier, which is found by Fredman and Khachiyan's test.
+    pub fn unresolved(&self) -> Option<Bits<N>> {
+        let complements = MonotoneFunction::new(self.lower_frontier.complement().iter().collect());
+
+        self.hypothesis().duality_witness(&complements)
+    }
+
+    /// Whether the frontiers determine every point, in which case the
+    /// hypothesis is the oracle.
+    pub fn is_complete(&self) -> bool {
+        self.unresolved().is_none()
+    }
+
+    pub fn graph(&self) -> Graph<(Bits<N>, &str), (), Undirected> {
+        let mut graph = Graph::<(Bits<N>, &str), (), Undirected>::new_undirected();
+        let mut history = HashMap::<Bits<N>, NodeIndex>::new();
+
+        for (b, _) in Bits::<N>::gray_code() {
+            let mut done = false;
+
+            for implicant in self.lower_frontier.iter() {
+                if b == implicant {
+                    let i = graph.add_node((b, "L"));
+                    history.insert(b, i);
+                    done = true;
+
+                    break;
+                } else if b < implicant {
+                    let i = graph.add_node((b, "X"));
+                    history.insert(b, i);
+                    done = true;
+
+                    break;
+                }
+            }
+
+            for implicant in self.upper_frontier.iter() {
+                if b == implicant {
+                    let i = graph.add_node((b, "U"));
+                    history.insert(b, i);
+                    done = true;
+
+                    break;
+                } else if b > implicant {
+                    let i = graph.add_node((b, "X"));
+                    history.insert(b, i);
+                    done = true;
+
+                    break;
+                }
+            }
+
+            if !done {
+                let i = graph.add_node((b, ""));
+                history.insert(b, i);
+            }
+        }
+
+        for i0 in graph.node_indices() {
+            for b1 in graph[i0].0.horizon(false) {
+                let i1 = history.get(&b1).unwrap();
+                let _ = graph.add_edge(*i1, i0, ());
+            }
+        }
+
+        graph
+    }
+}
+
+/// Runtime-width counterpart of `Learner<N>`.
+pub struct DynLearner {
+    oracle: DynMonotoneFunction,
+    lower_frontier: BTreeSet<Graded<DynBits>>,
+    upper_frontier: BTreeSet<Graded<DynBits>>,
+    iterations: usize,
+    rng: StdRng,
+}
+
+impl DynLearner {
+    pub fn new(oracle: DynMonotoneFunction) -> Self {
+        Self::with_config(oracle, LearnerConfig::default())
+    }
+
+    pub fn with_config(oracle: DynMonotoneFunction, config: LearnerConfig) -> Self {
+        Self {
+            oracle,
+            lower_frontier: BTreeSet::new(),
+            upper_frontier: BTreeSet::new(),
+            iterations: 0,
+            rng: config.rng(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.oracle.len()
+    }
+
+    pub fn iterate(&mut self) {
+        if self.iterations == 0 {
+            assert!(self.lower_frontier.is_empty());
+            assert!(self.upper_frontier.is_empty());
+
+            let eet = DynBits::new(self.len(), false);
+            let tee = DynBits::new(self.len(), true);
+            let x = eet.rand_midpoint_with(&tee, &mut self.rng).unwrap();
+
+            if self.oracle.call(&x) {
+                self.upper_frontier.insert(Graded(x));
+            } else {
+                self.lower_frontier.insert(Graded(x));
+            }
+        }
+
+        self.iterations += 1;
+    }
+
+    pub fn graph(&self) -> Graph<(DynBits, &str), (), Undirected> {
+        let mut graph = Graph::<(DynBits, &str), (), Undirected>::new_undirected();
+        let mut history = HashMap::<DynBits, NodeIndex>::new();
+
+        for (b, _) in DynBits::gray_code(self.len()) {
+            let mut label = "";
+
+            for Graded(implicant) in &self.lower_frontier {
+                if b == *implicant {
+                    label = "L";
+
+                    break;
+                } else if b < *implicant {
+                    label = "X";
+
+                    break;
+                }
+            }
+
+            for Graded(implicant) in &self.upper_frontier {
+                if b == *implicant {
+                    label = "U";
+
+                    break;
+                } else if b > *implicant {
+                    label = "X";
+
+                    break;
+                }
+            }
+
+            let i = graph.add_node((b.clone(), label));
+            history.insert(b, i);
+        }
+
+        for i0 in graph.node_indices() {
+            for b1 in graph[i0].0.horizon(false) {
+                let i1 = history.get(&b1).unwrap();
+                let _ = graph.add_edge(*i1, i0, ());
+            }
+        }
+
+        graph
+    }
+}
@@ -1,199 +1,2033 @@
-use std::collections::HashSet;
+#![cfg_attr(all(test, feature = "bench"), feature(test))]
+
+mod antichain;
 mod bits;
-use bits::Bits;
+mod cube;
+mod dual;
+mod dynbits;
+mod eval;
+mod family;
+mod function;
+mod gray;
+mod interval;
+mod learner;
+mod minimize;
+mod order;
+mod packed;
+mod perm;
+mod rank;
+mod sample;
+mod shadow;
+mod truth;
 mod util;
-use petgraph::{
-    dot::{Config, Dot},
-    graph::NodeIndex,
-    Graph, Undirected,
-};
-use std::{collections::HashMap, fs::File, io::Write, str::FromStr};
-
-#[derive(Clone, Debug)]
-pub struct MonotoneFunction<const N: usize> {
-    implicants: HashSet<Bits<N>>,
+use dynbits::DynBits;
+use function::DynMonotoneFunction;
+use learner::DynLearner;
+use petgraph::dot::{Config, Dot};
+use std::{env, fs::File, io::Write, process, str::FromStr};
+
+/// Implicants are taken from the command line, e.g. `switches 1010 0110`, and
+/// fix the dimension of the cube.
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    if args.is_empty() {
+        args.push("1010".to_string());
+    }
+
+    let implicants: Vec<DynBits> = args
+        .iter()
+        .map(|arg| DynBits::from_str(arg).expect("Implicants must be binary strings"))
+        .collect();
+    let k = implicants[0].len();
+
+    let f = match DynMonotoneFunction::new(k, implicants) {
+        Ok(f) => f,
+        Err(err) => {
+            eprintln!("Implicants must all have length {}: {}", k, err);
+            process::exit(1);
+        }
+    };
+
+    let mut learner = DynLearner::new(f);
+
+    let dot = format!(
+        "{:?}",
+        Dot::with_config(&learner.graph(), &[Config::EdgeNoLabel])
+    );
+    let mut out = File::create("./test0.dot").expect("Unable to create file");
+    out.write_all(dot.as_bytes()).expect("Unable to write data");
+
+    learner.iterate();
+    learner.iterate();
+
+    let dot = format!(
+        "{:?}",
+        Dot::with_config(&learner.graph(), &[Config::EdgeNoLabel])
+    );
+    let mut out = File::create("./test1.dot").expect("Unable to create file");
+    out.write_all(dot.as_bytes()).expect("Unable to write data");
 }
 
-impl<const N: usize> MonotoneFunction<N> {
-    pub fn new(implicants: Vec<Bits<N>>) -> Self {
-        let mut reduced = HashSet::<Bits<N>>::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bits::Bits;
+    use float_cmp::approx_eq;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::collections::{HashMap, HashSet};
+
+    const N: usize = 8;
+
+    #[test]
+    fn test_midpoint_distribution() {
+        let eet = Bits::<N>::new(false);
+        let tee = Bits::<N>::new(true);
+        let count = 100000_usize;
+        let mut counts = [0_usize; N];
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for _ in 0..count {
+            let t0 = eet.rand_midpoint_with(&tee, &mut rng).unwrap();
 
-        'outer: for implicant1 in &implicants {
-            for implicant2 in &implicants {
-                if *implicant1 > *implicant2 {
-                    continue 'outer;
+            for j in 0..t0.len() {
+                if t0[j] {
+                    counts[j] += 1;
                 }
             }
+        }
+
+        let mut dist = [0_f64; N];
 
-            reduced.insert(*implicant1);
+        for i in 0..N {
+            dist[i] = counts[i] as f64 / count as f64;
+            assert!(approx_eq!(f64, dist[i], 0.5, epsilon = 0.01));
         }
 
-        Self {
-            implicants: reduced,
+        println!("{:?}", dist);
+    }
+
+    fn assert_packed_matches_bools<const M: usize>(rng: &mut StdRng) {
+        for _ in 0..200 {
+            let xs: Vec<bool> = (0..M).map(|_| rng.gen_bool(0.5)).collect();
+            let ys: Vec<bool> = (0..M).map(|_| rng.gen_bool(0.5)).collect();
+            let s: String = xs.iter().map(|x| if *x { '1' } else { '0' }).collect();
+            let t: String = ys.iter().map(|y| if *y { '1' } else { '0' }).collect();
+            let a = Bits::<M>::from_str(&s).unwrap();
+            let b = Bits::<M>::from_str(&t).unwrap();
+            let r = rng.gen_range(0..=M);
+
+            assert_eq!(a.to_string(), s);
+            assert_eq!(a.count_ones(), xs.iter().filter(|x| **x).count());
+            assert_eq!(a <= b, xs.iter().zip(&ys).all(|(x, y)| !x || *y));
+            assert_eq!(
+                a.ones().collect::<Vec<_>>(),
+                (0..M).filter(|i| xs[*i]).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                a.zeroes().collect::<Vec<_>>(),
+                (0..M).filter(|i| !xs[*i]).collect::<Vec<_>>()
+            );
+
+            for i in 0..M {
+                assert_eq!((a & b)[i], xs[i] & ys[i]);
+                assert_eq!((a | b)[i], xs[i] | ys[i]);
+                assert_eq!((a ^ b)[i], xs[i] ^ ys[i]);
+                assert_eq!((!a)[i], !xs[i]);
+                assert_eq!((a << r)[i], i + r < M && xs[i + r]);
+                assert_eq!((a >> r)[i], i >= r && xs[i - r]);
+            }
+
+            let mut c = a;
+            c[3] = !c[3];
+            c[65] = true;
+            assert_eq!(c[3], !xs[3]);
+            assert!(c[65]);
+
+            // Writes through `IndexMut` are seen by every reader, pending or not
+            let mut d = a;
+            d.set(3, !xs[3]);
+            d.set(65, true);
+            assert_eq!(c, d);
+            assert_eq!(c.count_ones(), d.count_ones());
+            assert_eq!(c.to_string(), d.to_string());
+            assert_eq!(c.ones().collect::<Vec<_>>(), d.ones().collect::<Vec<_>>());
+            assert_eq!(HashSet::from([c]), HashSet::from([d]));
+            assert_eq!(c ^ d, Bits::new(false));
+
+            c[M - 1] = !c[M - 1];
+            c[M - 1] = !c[M - 1];
+            assert_eq!(c, d);
         }
     }
 
-    pub fn call(&self, x: Bits<N>) -> bool {
-        self.implicants.iter().map(|a| *a & x == *a).any(|b| b)
+    #[test]
+    fn test_packed_bits_match_bools() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        assert_packed_matches_bools::<70>(&mut rng);
+        assert_packed_matches_bools::<300>(&mut rng);
     }
-}
 
-pub struct Learner<const N: usize> {
-    oracle: MonotoneFunction<N>,
-    lower_frontier: HashSet<Bits<N>>,
-    upper_frontier: HashSet<Bits<N>>,
-    iterations: usize,
-}
+    #[test]
+    fn test_dynbits_matches_bits() {
+        const M: usize = 6;
 
-impl<const N: usize> Learner<N> {
-    pub fn new(oracle: MonotoneFunction<N>) -> Self {
-        Self {
-            oracle,
-            lower_frontier: HashSet::new(),
-            upper_frontier: HashSet::new(),
-            iterations: 0,
+        for n in 0..2_u32.pow(M as u32) {
+            let a: Bits<M> = n.try_into().unwrap();
+            let b: Bits<M> = (n * 7 % 64).try_into().unwrap();
+            let (x, y) = (DynBits::from(a), DynBits::from(b));
+
+            assert_eq!(x.to_string(), a.to_string());
+            assert_eq!(Bits::<M>::try_from(&x).unwrap(), a);
+            assert!(Bits::<5>::try_from(&x).is_err());
+            assert_eq!(a.partial_cmp(&b), x.partial_cmp(&y));
+            assert_eq!((a & b).to_string(), (&x & &y).to_string());
+            assert_eq!((a << 2).to_string(), (x.clone() << 2).to_string());
+            assert_eq!(
+                a.upper_shadow().map(|b| b.to_string()).collect::<Vec<_>>(),
+                x.upper_shadow().map(|y| y.to_string()).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                a.lower_shadow().map(|b| b.to_string()).collect::<Vec<_>>(),
+                x.lower_shadow().map(|y| y.to_string()).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                a.horizon(true).map(|b| b.to_string()).collect::<Vec<_>>(),
+                x.horizon(true).map(|y| y.to_string()).collect::<Vec<_>>()
+            );
+
+            if let Ok(paths) = a.paths(&b) {
+                let strings = |path: Vec<String>| path.join(" ");
+
+                assert_eq!(
+                    paths
+                        .rev()
+                        .map(|path| strings(path.iter().map(|b| b.to_string()).collect()))
+                        .collect::<Vec<_>>(),
+                    x.paths(&y)
+                        .unwrap()
+                        .rev()
+                        .map(|path| strings(path.iter().map(|y| y.to_string()).collect()))
+                        .collect::<Vec<_>>()
+                );
+                assert_eq!(
+                    a.midpoints(&b).unwrap().size_hint(),
+                    x.midpoints(&y).unwrap().size_hint()
+                );
+                assert_eq!(
+                    a.midpoints(&b)
+                        .unwrap()
+                        .map(|b| b.to_string())
+                        .collect::<Vec<_>>(),
+                    x.midpoints(&y)
+                        .unwrap()
+                        .map(|y| y.to_string())
+                        .collect::<Vec<_>>()
+                );
+                assert_eq!(
+                    a.converge(&b)
+                        .unwrap()
+                        .rev()
+                        .map(|(l, b)| (l, b.to_string()))
+                        .collect::<Vec<_>>(),
+                    x.converge(&y)
+                        .unwrap()
+                        .rev()
+                        .map(|(l, y)| (l, y.to_string()))
+                        .collect::<Vec<_>>()
+                );
+            }
         }
     }
 
-    pub fn iterate(&mut self) {
-        if self.iterations == 0 {
-            assert!(self.lower_frontier.is_empty());
-            assert!(self.upper_frontier.is_empty());
+    #[test]
+    fn test_bit_order_conversions() {
+        use bits::BitOrder::{LsbFirst, MsbFirst};
 
-            let eet = Bits::<N>::new(false);
-            let tee = Bits::<N>::new(true);
-            let x = eet.rand_midpoint(&tee).unwrap();
+        for x in 0..2_u16.pow(12) {
+            for order in [MsbFirst, LsbFirst] {
+                let a = Bits::<12>::from_uint(x, order).unwrap();
+                let bytes = match order {
+                    MsbFirst => x.to_be_bytes(),
+                    LsbFirst => x.to_le_bytes(),
+                };
 
-            if self.oracle.call(x) {
-                self.upper_frontier.insert(x);
-            } else {
-                self.lower_frontier.insert(x);
+                assert_eq!(a.to_uint::<u16>(order).unwrap(), x);
+                assert_eq!(a.to_bytes(order), bytes.to_vec());
+                assert_eq!(Bits::<12>::from_bytes(&bytes, order).unwrap(), a);
+                assert_eq!(
+                    Bits::<70>::from_uint(x, order)
+                        .unwrap()
+                        .to_uint::<u128>(order)
+                        .unwrap(),
+                    x as u128
+                );
+            }
+
+            let a = Bits::<12>::try_from(x).unwrap();
+            assert_eq!(a, Bits::<12>::from_uint(x, MsbFirst).unwrap());
+            assert_eq!(u16::try_from(a).unwrap(), x);
+        }
+
+        let a = Bits::<4>::from_str("0101").unwrap();
+        assert_eq!(u8::try_from(a).unwrap(), 5);
+        assert_eq!(a.to_uint::<u8>(LsbFirst).unwrap(), 10);
+        assert_eq!(Bits::<4>::from([false, true, false, true]), a);
+        assert!(Bits::<3>::try_from(&[true, false][..]).is_err());
+
+        let err = Bits::<4>::try_from(0x1f_u8).unwrap_err();
+        assert_eq!((err.width, err.capacity), (5, 4));
+
+        let err = Bits::<12>::new(true).to_uint::<u8>(LsbFirst).unwrap_err();
+        assert_eq!((err.width, err.capacity), (12, 8));
+    }
+
+    #[test]
+    fn test_total_orders() {
+        use bits::BitOrder::{LsbFirst, MsbFirst};
+        use order::{Colex, Graded, Lex};
+        use std::collections::BTreeSet;
+
+        let cube: Vec<Bits<5>> = (0..32_u8).map(|x| x.try_into().unwrap()).collect();
+        let lex: BTreeSet<_> = cube.iter().map(|b| Lex(*b)).collect();
+        let colex: BTreeSet<_> = cube.iter().map(|b| Colex(*b)).collect();
+        let graded: Vec<_> = cube
+            .iter()
+            .map(|b| Graded(*b))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|g| g.0)
+            .collect();
+
+        assert!(lex
+            .iter()
+            .map(|b| b.0.to_uint::<u8>(MsbFirst).unwrap())
+            .eq(0..32));
+        assert!(colex
+            .iter()
+            .map(|b| b.0.to_uint::<u8>(LsbFirst).unwrap())
+            .eq(0..32));
+        assert!(graded
+            .windows(2)
+            .all(|w| w[0].count_ones() <= w[1].count_ones()));
+
+        for (i, a) in graded.iter().enumerate() {
+            for b in &graded[i + 1..] {
+                assert_ne!(b.partial_cmp(a), Some(std::cmp::Ordering::Less));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rank_unrank() {
+        use order::Colex;
+        use std::collections::BTreeSet;
+
+        const M: usize = 7;
+
+        for r in 0..2_u128.pow(M as u32) {
+            assert_eq!(Bits::<M>::unrank(r).unwrap().rank(), r);
+        }
+
+        // Wide points rank fine while their high bits are clear
+        let wide = Bits::<200>::unrank(u128::MAX).unwrap();
+
+        assert_eq!(wide.rank(), u128::MAX);
+        assert!(std::panic::catch_unwind(|| (wide >> 1).rank()).is_err());
+        assert_eq!(
+            (Bits::<200>::new(true) >> 197).layer_rank(),
+            util::binomial(200, 3) - 1
+        );
+        assert!(std::panic::catch_unwind(|| (Bits::<200>::new(true) >> 100).layer_rank()).is_err());
+
+        for k in 0..=M {
+            let layer = Bits::<M>::layer(k);
+            let expected: Vec<_> = (0..2_u8.pow(M as u32))
+                .map(|x| Bits::<M>::try_from(x).unwrap())
+                .filter(|b| b.count_ones() == k)
+                .map(Colex)
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .map(|c| c.0)
+                .collect();
+
+            assert_eq!(layer.total(), Some(expected.len() as u128));
+            assert_eq!(layer.clone().rev().count(), expected.len());
+            assert_eq!(layer.collect::<Vec<_>>(), expected);
+
+            for (r, b) in expected.iter().enumerate() {
+                assert_eq!(b.layer_rank(), r as u128);
+                assert_eq!(Bits::<M>::layer_unrank(k, r as u128), Some(*b));
+            }
+        }
+
+        let a = Bits::<M>::from_str("0100100").unwrap();
+        let b = Bits::<M>::from_str("1101110").unwrap();
+
+        for k in 0..=3 {
+            let layer = b.interval_layer(&a, k).unwrap();
+            let midpoints = a.midpoints(&b).unwrap();
+
+            assert_eq!(midpoints.total(), Some(6));
+
+            for (r, x) in layer.enumerate() {
+                assert!(a <= x && x <= b);
+                assert_eq!(x.count_ones(), 2 + k);
+                assert_eq!(x.interval_layer_rank(&a, &b), Some(r as u128));
+                assert_eq!(a.interval_layer_unrank(&b, k, r as u128), Some(x));
             }
         }
 
-        self.iterations += 1;
+        assert_eq!(Bits::<M>::new(true).interval_layer_rank(&a, &b), None);
     }
 
-    pub fn graph(&self) -> Graph<(Bits<N>, &str), (), Undirected> {
-        let mut graph = Graph::<(Bits<N>, &str), (), Undirected>::new_undirected();
-        let mut history = HashMap::<Bits<N>, NodeIndex>::new();
+    #[test]
+    fn test_minimal_change_orders() {
+        use std::collections::HashSet;
+        use util::binomial;
+
+        const M: usize = 8;
+
+        let a = Bits::<M>::from_str("00100000").unwrap();
+        let b = Bits::<M>::from_str("10111011").unwrap();
+        let mut seen = HashSet::new();
+        let mut previous = a;
+
+        for (x, flipped) in a.interval_gray_code(&b).unwrap() {
+            assert!(a <= x && x <= b);
+            assert!(seen.insert(x));
+
+            if let Some(i) = flipped {
+                assert_eq!(previous ^ x, Bits::<M>::unrank(1 << i).unwrap());
+            }
+
+            previous = x;
+        }
+
+        assert_eq!(seen.len(), 32);
+        assert_eq!(
+            Vec::from_iter(DynBits::gray_code(M).map(|(x, i)| (x.to_string(), i))),
+            Vec::from_iter(Bits::<M>::gray_code().map(|(x, i)| (x.to_string(), i)))
+        );
+
+        let wide = DynBits::gray_code(200);
+        let flips: Vec<Option<usize>> = wide.clone().take(8).map(|(_, i)| i).collect();
+
+        assert_eq!(wide.size_hint(), (usize::MAX, None));
+        assert_eq!(
+            flips,
+            [
+                None,
+                Some(0),
+                Some(1),
+                Some(0),
+                Some(2),
+                Some(0),
+                Some(1),
+                Some(0)
+            ]
+        );
 
-        for n in 0..2_u64.pow(N as u32) {
-            let b: Bits<N> = n.try_into().unwrap();
-            let mut done = false;
+        for k in 0..=M {
+            let doors = Bits::<M>::revolving_door(k);
+            let mut seen = HashSet::new();
+            let mut previous = None;
 
-            for implicant in &self.lower_frontier {
-                if b == *implicant {
-                    let i = graph.add_node((b, "L"));
-                    history.insert(b, i);
-                    done = true;
+            assert_eq!(doors.total(), binomial(M, k));
 
-                    break;
-                } else if b < *implicant {
-                    let i = graph.add_node((b, "X"));
-                    history.insert(b, i);
-                    done = true;
+            for (x, swapped) in doors {
+                assert_eq!(x.count_ones(), k);
+                assert!(seen.insert(x));
 
-                    break;
+                if let Some((cleared, set)) = swapped {
+                    let mut y: Bits<M> = previous.unwrap();
+
+                    y.set(cleared, false);
+                    y.set(set, true);
+                    assert_eq!(x, y);
                 }
+
+                previous = Some(x);
             }
 
-            for implicant in &self.upper_frontier {
-                if b == *implicant {
-                    let i = graph.add_node((b, "U"));
-                    history.insert(b, i);
-                    done = true;
+            assert_eq!(seen.len() as u128, binomial(M, k));
+        }
+    }
+
+    #[test]
+    fn test_interval_algebra() {
+        use interval::Interval;
+
+        const M: usize = 5;
+
+        let cube: Vec<Bits<M>> = Interval::cube().iter().collect();
+        let intervals: Vec<Interval<M>> = cube
+            .iter()
+            .flat_map(|a| cube.iter().filter_map(move |b| a.interval(b).ok()))
+            .collect();
 
-                    break;
-                } else if b > *implicant {
-                    let i = graph.add_node((b, "X"));
-                    history.insert(b, i);
-                    done = true;
+        assert_eq!(cube.len(), 32);
 
-                    break;
+        for s in intervals.iter().step_by(7) {
+            let points: Vec<_> = s.iter().collect();
+
+            assert_eq!(points.len() as u128, s.size().unwrap());
+            assert!(points.iter().all(|x| s.contains(x)));
+            assert_eq!(cube.iter().filter(|x| s.contains(x)).count(), points.len());
+
+            for t in intervals.iter().step_by(5) {
+                let common = s.intersection(t);
+                let hull = s.hull(t);
+                let pieces = s.difference(t);
+
+                for x in &cube {
+                    let (in_s, in_t) = (s.contains(x), t.contains(x));
+
+                    assert_eq!(common.is_some_and(|c| c.contains(x)), in_s && in_t);
+                    assert!(!(in_s || in_t) || hull.contains(x));
+                    assert_eq!(
+                        pieces.iter().filter(|p| p.contains(x)).count(),
+                        (in_s && !in_t) as usize
+                    );
                 }
+
+                assert_eq!(s.is_subinterval(t), points.iter().all(|x| t.contains(x)));
             }
+        }
+
+        let wide = Interval::<130>::cube();
+        let mut lo = Bits::<130>::new(false);
+        let mut hi = Bits::<130>::new(true);
+
+        lo.set(3, true);
+        hi.set(127, false);
+        hi.set(128, false);
 
-            if !done {
-                let i = graph.add_node((b, ""));
-                history.insert(b, i);
+        assert_eq!(wide.size(), None);
+        assert_eq!(Interval::new(&lo, &hi).unwrap().size(), Some(1 << 127));
+        assert_eq!(wide.converge().next(), Some((0, Bits::new(false))));
+        assert!(wide.layer(65).next().is_some());
+        assert_eq!(wide.rand_midpoint().count_ones(), 65);
+
+        let corner = Interval::new(&hi, &hi).unwrap();
+
+        assert_eq!(corner.size(), Some(1));
+        assert_eq!(Vec::from_iter(corner.iter()), vec![hi]);
+    }
+
+    #[test]
+    fn test_cube_cover_tautology() {
+        use cube::{Cover, Cube};
+        use function::MonotoneFunction;
+        use rand::seq::SliceRandom;
+
+        const M: usize = 4;
+
+        let cube = Cube::<M>::from_str("1-0-").unwrap();
+        let other = Cube::<M>::from_str("100-").unwrap();
+
+        assert_eq!(cube.to_string(), "1-0-");
+        assert!(cube.contains_cube(&other));
+        assert_eq!(cube.cofactor(0, true).unwrap().to_string(), "--0-");
+        assert_eq!(cube.cofactor(2, true), None);
+        assert_eq!(
+            Cube::<M>::from_str("1-1-")
+                .unwrap()
+                .consensus(&Cube::from_str("0-11").unwrap())
+                .unwrap()
+                .to_string(),
+            "--11"
+        );
+        assert_eq!(Cube::from(interval::Interval::from(cube)), cube);
+
+        let f = MonotoneFunction::<M>::new(vec![Bits::from_str("1010").unwrap()]);
+        assert_eq!(f.to_string(), "1-1-");
+
+        let all: Vec<Cube<M>> = (0..81_u32)
+            .map(|mut x| {
+                (0..M)
+                    .map(|_| {
+                        let c = ['0', '1', '-'][(x % 3) as usize];
+                        x /= 3;
+                        c
+                    })
+                    .collect::<String>()
+                    .parse()
+                    .unwrap()
+            })
+            .collect();
+        let mut rng = StdRng::seed_from_u64(2);
+
+        for size in 0..12 {
+            for _ in 0..50 {
+                let cover: Cover<M> = all.choose_multiple(&mut rng, size).copied().collect();
+
+                assert_eq!(cover.is_tautology(), cover.minterms().len() == 16);
+                assert_eq!(cover.covers(&cube), cube.iter().all(|x| cover.contains(&x)));
             }
         }
+    }
+
+    #[test]
+    fn test_canonical_forms_and_automorphisms() {
+        use function::MonotoneFunction;
+        use itertools::Itertools;
+        use perm::Permutation;
+        use std::collections::HashSet;
+
+        const M: usize = 5;
 
-        for (b0, i0) in &history {
-            for b1 in b0.horizon(false) {
-                let i1 = history.get(&b1).unwrap();
-                let _ = graph.add_edge(*i1, *i0, ());
+        let all: Vec<Permutation<M>> = (0..M)
+            .permutations(M)
+            .map(|p| Permutation::try_from(<[usize; M]>::try_from(p).unwrap()).unwrap())
+            .collect();
+        let pi = Permutation::<M>::from_cycles(&[&[0, 3, 1], &[2, 4]]).unwrap();
+
+        assert_eq!(pi.to_string(), "(0 3 1)(2 4)");
+        assert!(pi.compose(&pi.inverse()).is_identity());
+
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for _ in 0..30 {
+            let implicants = (0..rng.gen_range(1..5))
+                .map(|_| Bits::<M>::unrank(rng.gen_range(0..32)).unwrap())
+                .collect();
+            let f = MonotoneFunction::new(implicants);
+            let (canonical, sigma) = f.canonical();
+
+            assert_eq!(f.permute(&sigma), canonical);
+
+            for tau in all.iter().step_by(11) {
+                assert_eq!(f.permute(tau).canonical().0, canonical);
+            }
+
+            let expected: HashSet<_> = all.iter().filter(|p| f.permute(p) == f).collect();
+            let mut group = HashSet::from([Permutation::<M>::identity()]);
+            let mut frontier = vec![Permutation::<M>::identity()];
+
+            while let Some(g) = frontier.pop() {
+                for h in f.automorphisms() {
+                    if group.insert(g.compose(&h)) {
+                        frontier.push(g.compose(&h));
+                    }
+                }
             }
+
+            assert_eq!(group.len(), expected.len());
         }
+    }
+
+    #[test]
+    fn test_seeded_runs_are_reproducible() {
+        use function::MonotoneFunction;
+        use learner::{Learner, LearnerConfig};
+
+        let eet = Bits::<64>::new(false);
+        let tee = Bits::<64>::new(true);
+        let xs: Vec<_> = (0..2)
+            .map(|_| {
+                let mut rng = StdRng::seed_from_u64(7);
+
+                (0..10)
+                    .map(|_| eet.rand_midpoint_with(&tee, &mut rng).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        assert_eq!(xs[0], xs[1]);
 
-        graph
+        let dots: Vec<String> = (0..2)
+            .map(|_| {
+                let f = MonotoneFunction::<6>::new(vec![Bits::from_str("101000").unwrap()]);
+                let mut learner = Learner::with_config(f, LearnerConfig::default().seed(7));
+
+                learner.iterate();
+
+                format!(
+                    "{:?}",
+                    Dot::with_config(&learner.graph(), &[Config::EdgeNoLabel])
+                )
+            })
+            .collect();
+
+        assert_eq!(dots[0], dots[1]);
+
+        let f = DynMonotoneFunction::new(6, vec![DynBits::from_str("101000").unwrap()]).unwrap();
+        let learner = DynLearner::new(f);
+        let graph = learner.graph();
+
+        assert_eq!(graph.node_count(), 64);
+        assert_eq!(graph.edge_count(), 6 * 32);
     }
-}
 
-const K: usize = 4;
+    /// Pearson's chi-squared test against the expected probabilities, with a
+    /// bound about four standard deviations above the mean.
+    fn assert_distribution<T: std::hash::Hash + Eq + std::fmt::Debug>(
+        samples: Vec<T>,
+        expected: &HashMap<T, f64>,
+    ) {
+        let count = samples.len() as f64;
+        let mut counts = HashMap::<T, usize>::new();
 
-fn main() {
-    let mut bs = Vec::<Bits<K>>::new();
+        for sample in samples {
+            assert!(expected.contains_key(&sample), "unexpected {:?}", sample);
+            *counts.entry(sample).or_default() += 1;
+        }
 
-    for n in 0..2_u32.pow(K as u32) {
-        let b = n.try_into().unwrap();
-        bs.push(b);
+        let chi2: f64 = expected
+            .iter()
+            .map(|(x, p)| {
+                let observed = *counts.get(x).unwrap_or(&0) as f64;
+                (observed - count * p).powi(2) / (count * p)
+            })
+            .sum();
+        let df = (expected.len() - 1) as f64;
+
+        assert!(
+            chi2 < df + 4.0 * (2.0 * df).sqrt(),
+            "chi2 {} for {} df",
+            chi2,
+            df
+        );
     }
 
-    let f = MonotoneFunction::<K>::new(vec![Bits::<K>::from_str("1010").unwrap()]);
+    fn uniform<T: std::hash::Hash + Eq>(xs: impl Iterator<Item = T>) -> HashMap<T, f64> {
+        let xs: Vec<T> = xs.collect();
+        let p = 1.0 / xs.len() as f64;
 
-    let mut learner = Learner::<K>::new(f);
+        xs.into_iter().map(|x| (x, p)).collect()
+    }
 
-    let dot = format!(
-        "{:?}",
-        Dot::with_config(&learner.graph(), &[Config::EdgeNoLabel])
-    );
-    let mut out = File::create("./test0.dot").expect("Unable to create file");
-    out.write_all(dot.as_bytes()).expect("Unable to write data");
+    /// A function of `size` random implicants, each setting every coordinate
+    /// with probability `p`.
+    pub(super) fn random_function<const N: usize>(
+        size: usize,
+        p: f64,
+        rng: &mut StdRng,
+    ) -> function::MonotoneFunction<N> {
+        let implicants = (0..size).map(|_| sample::biased(&interval::Interval::cube(), p, rng));
 
-    learner.iterate();
-    learner.iterate();
+        function::MonotoneFunction::new(implicants.collect())
+    }
 
-    let dot = format!(
-        "{:?}",
-        Dot::with_config(&learner.graph(), &[Config::EdgeNoLabel])
-    );
-    let mut out = File::create("./test1.dot").expect("Unable to create file");
-    out.write_all(dot.as_bytes()).expect("Unable to write data");
-}
+    #[test]
+    fn test_sampling_distributions() {
+        use interval::Interval;
+        use order::Colex;
+        use std::collections::BTreeSet;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use float_cmp::approx_eq;
+        let mut rng = StdRng::seed_from_u64(11);
+        let count = 20000;
+        let interval = Bits::<6>::from_str("010000")
+            .unwrap()
+            .interval(&Bits::from_str("011111").unwrap())
+            .unwrap();
 
-    const N: usize = 8;
+        assert_distribution(
+            (0..count)
+                .map(|_| sample::layer(&interval, 2, &mut rng).unwrap())
+                .collect(),
+            &uniform(interval.iter().filter(|x| x.count_ones() == 3)),
+        );
+
+        let biased: HashMap<Bits<6>, f64> = interval
+            .iter()
+            .map(|x| {
+                let k = x.count_ones() as i32 - 1;
+                (x, 0.3_f64.powi(k) * 0.7_f64.powi(4 - k))
+            })
+            .collect();
+        assert_distribution(
+            (0..count)
+                .map(|_| sample::biased(&interval, 0.3, &mut rng))
+                .collect(),
+            &biased,
+        );
+
+        assert_distribution(
+            (0..count)
+                .map(|_| sample::chain(&interval, &mut rng))
+                .collect(),
+            &uniform(interval.paths()),
+        );
+
+        let cube = Interval::<3>::cube();
+        let points: Vec<Bits<3>> = cube.iter().collect();
+        let pairs = points.iter().flat_map(|x| {
+            points
+                .iter()
+                .filter(move |y| x.partial_cmp(y).is_none())
+                .map(move |y| BTreeSet::from([Colex(*x), Colex(*y)]))
+        });
+        assert_distribution(
+            (0..count)
+                .map(|_| {
+                    let members = sample::antichain(&cube, 2, &mut rng).unwrap();
+                    members.into_iter().map(Colex).collect::<BTreeSet<_>>()
+                })
+                .collect(),
+            &uniform(pairs.collect::<HashSet<_>>().into_iter()),
+        );
+        assert!(sample::antichain(&cube, 4, &mut rng).is_none());
+
+        let layer: Vec<Bits<3>> = Bits::<3>::layer(1).collect();
+        let pairs = layer.iter().flat_map(|x| {
+            layer
+                .iter()
+                .filter(move |y| x != *y)
+                .map(move |y| BTreeSet::from([Colex(*x), Colex(*y)]))
+        });
+        assert_distribution(
+            (0..count)
+                .map(|_| {
+                    let members = sample::layer_antichain(&cube, 1, 2, &mut rng).unwrap();
+                    members.into_iter().map(Colex).collect::<BTreeSet<_>>()
+                })
+                .collect(),
+            &uniform(pairs.collect::<HashSet<_>>().into_iter()),
+        );
+
+        let generators = ["1100", "0110", "0001"].map(|g| Bits::<4>::from_str(g).unwrap());
+        let above = |x: &Bits<4>| generators.iter().any(|g| g <= x);
+        let below = |x: &Bits<4>| generators.iter().any(|g| x <= g);
+        assert_distribution(
+            (0..count)
+                .map(|_| sample::up_set(&generators, &mut rng).unwrap())
+                .collect(),
+            &uniform(Interval::<4>::cube().iter().filter(above)),
+        );
+        assert_distribution(
+            (0..count)
+                .map(|_| sample::down_set(&generators, &mut rng).unwrap())
+                .collect(),
+            &uniform(Interval::<4>::cube().iter().filter(below)),
+        );
+    }
 
     #[test]
-    fn test_midpoint_distribution() {
-        let eet = Bits::<N>::new(false);
-        let tee = Bits::<N>::new(true);
-        let count = 100000_usize;
-        let mut counts = [0_usize; N];
+    fn test_symmetric_chains() {
+        use interval::Interval;
+        use util::binomial;
 
-        for _ in 0..count {
-            let t0 = eet.rand_midpoint(&tee).unwrap();
+        fn check<const N: usize>(interval: Interval<N>) {
+            let d = interval.dimension();
+            let chains: Vec<Vec<Bits<N>>> = interval.symmetric_chains().collect();
+            let mut covered = HashSet::new();
 
-            for j in 0..t0.len() {
-                if t0[j] {
-                    counts[j] += 1;
+            assert_eq!(chains.len() as u128, binomial(d, d / 2));
+
+            for chain in &chains {
+                let bottom = chain[0].count_ones() - interval.lo().count_ones();
+                let top = interval.hi().count_ones() - chain.last().unwrap().count_ones();
+
+                assert_eq!(bottom, top);
+
+                for pair in chain.windows(2) {
+                    assert!(pair[0] <= pair[1]);
+                    assert_eq!(pair[0].distance(&pair[1]).unwrap(), 1);
+                }
+
+                for x in chain {
+                    assert!(interval.contains(x));
+                    assert!(covered.insert(*x));
                 }
             }
+
+            assert_eq!(covered.len() as u128, interval.size().unwrap());
         }
 
-        let mut dist = [0_f64; N];
+        for d in 0..=7 {
+            let mut hi = Bits::<7>::new(false);
 
-        for i in 0..N {
-            dist[i] = counts[i] as f64 / count as f64;
-            assert!(approx_eq!(f64, dist[i], 0.5, epsilon = 0.01));
+            for i in 0..d {
+                hi.set(i, true);
+            }
+
+            check(Interval::new(&Bits::new(false), &hi).unwrap());
         }
 
-        println!("{:?}", dist);
+        check(Interval::<8>::cube());
+        check(
+            Bits::<9>::from_str("010010000")
+                .unwrap()
+                .interval(&Bits::from_str("110111011").unwrap())
+                .unwrap(),
+        );
+        assert_eq!(
+            Bits::<4>::symmetric_chains().count(),
+            Interval::<4>::cube().symmetric_chains().count()
+        );
+    }
+
+    #[test]
+    fn test_path_counting_ranking_and_sampling() {
+        use std::collections::BTreeSet;
+        use util::factorial;
+
+        let lo = Bits::<6>::from_str("100000").unwrap();
+        let hi = Bits::<6>::from_str("101111").unwrap();
+
+        for (a, b) in [(lo, hi), (hi, lo), (lo, lo)] {
+            let d = a.distance(&b).unwrap();
+            let count = a.count_paths(&b).unwrap();
+            let mut walk = a.path_walk(&b).unwrap();
+            let mut walked = Vec::new();
+
+            assert_eq!(count, factorial(d));
+
+            while let Some(path) = walk.next_path() {
+                assert_eq!(Bits::path_rank(path), Some(walked.len() as u128));
+                assert_eq!(
+                    a.path_unrank(&b, walked.len() as u128).as_deref(),
+                    Some(path)
+                );
+
+                walked.push(path.to_vec());
+            }
+
+            assert_eq!(walked.len() as u128, count);
+            assert_eq!(a.path_unrank(&b, count), None);
+
+            let expected: BTreeSet<Vec<order::Colex<Bits<6>>>> = a
+                .paths(&b)
+                .unwrap()
+                .map(|path| path.into_iter().map(order::Colex).collect())
+                .collect();
+            let walked: BTreeSet<Vec<order::Colex<Bits<6>>>> = walked
+                .into_iter()
+                .map(|path| path.into_iter().map(order::Colex).collect())
+                .collect();
+
+            assert_eq!(walked, expected);
+        }
+
+        let skip =
+            ["100000", "101000", "101110", "101111"].map(|x| Bits::<6>::from_str(x).unwrap());
+        let back = ["100000", "101000", "100000"].map(|x| Bits::<6>::from_str(x).unwrap());
+
+        assert_eq!(Bits::path_rank(&skip), None);
+        assert_eq!(Bits::path_rank(&back), None);
+
+        let mut rng = StdRng::seed_from_u64(13);
+
+        assert_distribution(
+            (0..20000)
+                .map(|_| Bits::path_rank(&hi.rand_path_with(&lo, &mut rng).unwrap()).unwrap())
+                .collect(),
+            &uniform(0..factorial(4)),
+        );
+    }
+
+    #[test]
+    fn test_converge_levels_and_resume() {
+        let lo = Bits::<7>::from_str("0100100").unwrap();
+        let hi = Bits::<7>::from_str("1101111").unwrap();
+
+        for (start, end) in [(lo, hi), (hi, lo), (lo, lo)] {
+            let walk: Vec<(usize, Bits<7>)> = start.converge(&end).unwrap().collect();
+            let interval = start.interval(&end).unwrap();
+            let points: HashSet<Bits<7>> = walk.iter().map(|(_, x)| *x).collect();
+
+            assert_eq!(walk.len() as u128, interval.size().unwrap());
+            assert_eq!(points, interval.iter().collect());
+            assert!(walk.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+
+            for (level, x) in &walk {
+                assert_eq!(start.distance(x).unwrap(), *level);
+            }
+
+            let mut converge = start.converge(&end).unwrap();
+
+            for j in 0..=walk.len() {
+                let resumed: Vec<(usize, Bits<7>)> = start
+                    .converge_from(&end, converge.position())
+                    .unwrap()
+                    .collect();
+
+                assert_eq!(resumed, walk[j..]);
+                converge.next();
+            }
+
+            // Stepping from either end, or both in turn, meets the same points
+            let mut backwards: Vec<_> = start.converge(&end).unwrap().rev().collect();
+            let mut converge = start.converge(&end).unwrap();
+            let (mut front, mut back) = (Vec::new(), Vec::new());
+
+            backwards.reverse();
+
+            while let Some(x) = converge.next() {
+                front.push(x);
+                back.extend(converge.next_back());
+            }
+
+            back.reverse();
+            front.extend(back);
+
+            assert_eq!(backwards, walk);
+            assert_eq!(front, walk);
+        }
+
+        let ascending: Vec<Bits<7>> = lo.converge(&hi).unwrap().map(|(_, x)| x).collect();
+        let descending: Vec<Bits<7>> = (!lo).converge(&!hi).unwrap().map(|(_, x)| !x).collect();
+
+        assert_eq!(ascending, descending);
+        assert_eq!(
+            lo.upper_shadow().collect::<HashSet<_>>(),
+            interval::Interval::upper_shadow(&lo).iter().collect()
+        );
+        assert_eq!(
+            hi.lower_shadow().collect::<HashSet<_>>(),
+            interval::Interval::lower_shadow(&hi).iter().collect()
+        );
+        assert!(hi
+            .lower_shadow()
+            .zip(hi.lower_shadow().skip(1))
+            .all(|(x, y)| x.count_ones() >= y.count_ones()));
+    }
+
+    #[test]
+    fn test_layers_between_points() {
+        let lo = Bits::<8>::from_str("00100000").unwrap();
+        let hi = Bits::<8>::from_str("10111101").unwrap();
+
+        for (a, b) in [(lo, hi), (hi, lo)] {
+            let d = a.distance(&b).unwrap();
+            let interval = a.interval(&b).unwrap();
+
+            for k in 0..=d {
+                let layer = interval.layer(k);
+                let points: Vec<Bits<8>> = layer.clone().collect();
+
+                assert_eq!(layer.total(), Some(points.len() as u128));
+                assert_eq!(points.len() as u128, util::binomial(d, k));
+
+                for (j, x) in points.iter().enumerate() {
+                    assert_eq!(lo.distance(x).unwrap(), k);
+                    assert_eq!(layer.get(j as u128), Some(*x));
+                    assert_eq!(layer.clone().nth(j), Some(*x));
+                }
+
+                assert_eq!(layer.get(points.len() as u128), None);
+            }
+
+            // Both count up from the bottom whichever end they start at
+            for (q, k) in [(0.0, 0), (0.25, 1), (0.5, 2), (0.75, 3), (1.0, 5)] {
+                let layer: Vec<Bits<8>> = a.quantile_layer(&b, q).unwrap().collect();
+
+                assert_eq!(layer, Vec::from_iter(a.interval_layer(&b, k).unwrap()));
+                assert_eq!(layer, Vec::from_iter(b.quantile_layer(&a, q).unwrap()));
+                assert!(layer.iter().all(|x| lo.distance(x).unwrap() == k));
+            }
+
+            assert!(a
+                .middle_layer(&b)
+                .unwrap()
+                .all(|x| lo.distance(&x).unwrap() == 2));
+
+            let midpoints = a.midpoints(&b).unwrap();
+            let points: Vec<Bits<8>> = midpoints.clone().collect();
+
+            assert_eq!(midpoints.size_hint(), (points.len(), Some(points.len())));
+            assert_eq!(midpoints.total(), Some(20));
+
+            for (j, x) in points.iter().enumerate() {
+                assert_eq!(midpoints.get(j as u128), Some(*x));
+            }
+        }
+
+        let c = Bits::<8>::new(true);
+        let midpoints: Vec<Bits<8>> = lo.midpoints(&c).unwrap().collect();
+
+        assert_eq!(midpoints.len(), 70);
+        assert!(midpoints
+            .iter()
+            .all(|x| lo.distance(x).unwrap() == 3 || lo.distance(x).unwrap() == 4));
+    }
+
+    /// Checks the size hints against the items actually produced, from
+    /// the front, the back and both ends at once, and that the iterator stays
+    /// exhausted.
+    fn assert_exact<I>(iter: I)
+    where
+        I: Clone + DoubleEndedIterator + std::iter::FusedIterator,
+        I::Item: std::fmt::Debug + PartialEq,
+    {
+        let forward: Vec<I::Item> = iter.clone().collect();
+        let mut backward: Vec<I::Item> = iter.clone().rev().collect();
+
+        backward.reverse();
+        assert_eq!(iter.size_hint(), (forward.len(), Some(forward.len())));
+        assert_eq!(backward, forward);
+
+        let mut iter = iter;
+        let (mut front, mut back) = (Vec::new(), Vec::new());
+
+        for j in 0..forward.len() {
+            assert_eq!(
+                iter.size_hint(),
+                (forward.len() - j, Some(forward.len() - j))
+            );
+
+            if j % 3 == 1 {
+                back.push(iter.next_back().unwrap());
+            } else {
+                front.push(iter.next().unwrap());
+            }
+        }
+
+        back.reverse();
+        front.extend(back);
+        assert_eq!(front, forward);
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn test_binomial_coefficients() {
+        use util::{binomial, checked_binomial};
+
+        let mut row = vec![1_u128];
+
+        for n in 0..=130 {
+            for (k, c) in row.iter().enumerate() {
+                assert_eq!(checked_binomial(n, k), Some(*c));
+            }
+
+            assert_eq!(checked_binomial(n, n + 1), Some(0));
+
+            let next: Vec<Option<u128>> = (0..=n + 1)
+                .map(|k| {
+                    let left = if k > 0 { row[k - 1] } else { 0 };
+                    let right = row.get(k).copied().unwrap_or(0);
+
+                    left.checked_add(right)
+                })
+                .collect();
+
+            if next.iter().any(|c| c.is_none()) {
+                break;
+            }
+
+            row = next.into_iter().flatten().collect();
+        }
+
+        assert_eq!(
+            binomial(126, 63),
+            6_034_934_435_761_406_706_427_864_636_568_328_000
+        );
+        assert_eq!(binomial(127, 63), binomial(126, 62) + binomial(126, 63));
+        assert_eq!(checked_binomial(132, 66), None);
+
+        let layer = Bits::<127>::layer(63);
+
+        assert_eq!(layer.total(), Some(binomial(127, 63)));
+        assert_eq!(layer.size_hint(), (usize::MAX, None));
+        assert_eq!(layer.clone().next().unwrap().count_ones(), 63);
+        assert_eq!(layer.clone().next_back().unwrap().count_ones(), 63);
+    }
+
+    #[test]
+    fn test_exact_size_iterators() {
+        const M: usize = 5;
+
+        let points: Vec<Bits<M>> = interval::Interval::<M>::cube().iter().collect();
+
+        for a in &points {
+            assert_exact(a.zeroes());
+            assert_exact(a.ones());
+            assert_exact(a.horizon(false));
+            assert_exact(a.horizon(true));
+            assert_exact(a.upper_shadow());
+            assert_exact(a.lower_shadow());
+            assert_eq!(a.upper_shadow().total(), Some(1 << a.count_zeroes()));
+            assert_eq!(a.lower_shadow().total(), Some(1 << a.count_ones()));
+
+            for b in &points {
+                if a.partial_cmp(b).is_none() {
+                    continue;
+                }
+
+                let d = a.distance(b).unwrap();
+
+                assert_exact(a.midpoints(b).unwrap());
+                assert_exact(a.converge(b).unwrap());
+                assert_exact(a.paths(b).unwrap());
+                assert_eq!(a.converge(b).unwrap().total(), Some(1 << d));
+                assert_eq!(a.paths(b).unwrap().total(), Some(util::factorial(d)));
+
+                for k in 0..=d {
+                    assert_exact(a.interval_layer(b, k).unwrap());
+                }
+            }
+        }
+
+        let wide = Bits::<130>::new(false);
+
+        assert_eq!(wide.zeroes().len(), 130);
+        assert_eq!(wide.zeroes().next_back(), Some(129));
+        assert_eq!(wide.upper_shadow().total(), None);
+        assert_eq!(Bits::<130>::gray_code().total(), None);
+        assert_eq!(interval::Interval::<130>::cube().iter().total(), None);
+        assert_eq!(wide.paths(&!wide).unwrap().total(), None);
+    }
+
+    #[test]
+    fn test_wide_iterators() {
+        // Counts near and past 2^127 must neither panic nor claim to be exact
+        let shadow = Bits::<127>::new(false).upper_shadow();
+        let first: Vec<Bits<127>> = shadow.clone().take(3).collect();
+
+        assert_eq!(shadow.total(), Some(1 << 127));
+        assert_eq!(shadow.size_hint(), (usize::MAX, None));
+        assert_eq!(
+            first.iter().map(Bits::count_ones).collect::<Vec<_>>(),
+            [0, 1, 1]
+        );
+        assert_eq!(shadow.clone().next_back(), Some(Bits::new(true)));
+
+        let shadow = Bits::<126>::new(true).lower_shadow();
+
+        assert_eq!(shadow.total(), Some(1 << 126));
+        assert_eq!(shadow.size_hint(), (usize::MAX, None));
+        assert_eq!(shadow.clone().nth(2).unwrap().count_zeroes(), 1);
+
+        let mut converge = Bits::<130>::new(false).upper_shadow();
+
+        assert_eq!(converge.size_hint(), (usize::MAX, None));
+        assert_eq!(converge.next_back(), Some(Bits::new(true)));
+        assert_eq!(converge.nth(130).unwrap().count_ones(), 1);
+
+        let gray = Bits::<127>::gray_code();
+
+        assert_eq!(gray.total(), Some(1 << 127));
+        assert_eq!(gray.size_hint(), (usize::MAX, None));
+        assert_eq!(gray.take(4).count(), 4);
+
+        let points = interval::Interval::<127>::cube().iter();
+
+        assert_eq!(points.total(), Some(1 << 127));
+        assert_eq!(points.size_hint(), (usize::MAX, None));
+        assert_eq!(points.take(3).count(), 3);
+
+        let paths = Bits::<34>::new(false).paths(&Bits::new(true)).unwrap();
+
+        assert_eq!(paths.total(), Some(util::factorial(34)));
+        assert_eq!(paths.size_hint(), (usize::MAX, None));
+        assert_eq!(paths.take(2).count(), 2);
+
+        let midpoints = Bits::<127>::new(false).midpoints(&Bits::new(true)).unwrap();
+
+        assert_eq!(midpoints.total(), Some(2 * util::binomial(127, 63)));
+        assert_eq!(midpoints.size_hint(), (usize::MAX, None));
+        assert_eq!(midpoints.take(3).count(), 3);
+
+        // Layers too large to count are still walked from both ends
+        let mut midpoints = Bits::<140>::new(false).midpoints(&Bits::new(true)).unwrap();
+        let first: Vec<Bits<140>> = midpoints.by_ref().take(3).collect();
+
+        assert_eq!(midpoints.total(), None);
+        assert_eq!(midpoints.size_hint(), (usize::MAX, None));
+        assert!(first.iter().all(|x| x.count_ones() == 70));
+        assert_eq!(first[0], !Bits::new(false) << 70);
+        assert_eq!(
+            Vec::from_iter(first[1].ones()),
+            Vec::from_iter((0..69).chain([70]))
+        );
+        assert_eq!(midpoints.next_back(), Some(!Bits::new(false) >> 70));
+        assert_eq!(midpoints.nth(2).unwrap().count_ones(), 70);
+
+        let (lo, hi) = (DynBits::new(200, false), DynBits::new(200, true));
+        let mut midpoints = lo.midpoints(&hi).unwrap();
+
+        assert_eq!(midpoints.total(), None);
+        assert!(midpoints.by_ref().take(3).all(|x| x.count_ones() == 100));
+        assert_eq!(midpoints.next_back().unwrap().count_ones(), 100);
+
+        // Probing a quarter and three quarters of the way along a wide chain
+        let (lo, hi) = (Bits::<200>::new(false), Bits::<200>::new(true));
+
+        for (q, k) in [(0.25, 50), (0.75, 150)] {
+            let mut layer = hi.quantile_layer(&lo, q).unwrap();
+            let rank = u128::MAX - 1;
+            let x = layer.get(rank).unwrap();
+
+            assert_eq!(layer.total(), None);
+            assert_eq!(layer.next(), Some(Bits::new(true) << (200 - k)));
+            assert_eq!(layer.next_back(), Some(Bits::new(true) >> (200 - k)));
+            assert!(layer.by_ref().take(3).all(|x| x.count_ones() == k));
+            assert_eq!(x.count_ones(), k);
+            assert_eq!(x.layer_rank(), rank);
+            assert_eq!(lo.interval_layer_unrank(&hi, k, rank), Some(x));
+        }
+    }
+
+    #[test]
+    fn test_kruskal_katona() {
+        use util::binomial;
+
+        for k in 1..=6 {
+            for m in 0..500 {
+                let terms = shadow::cascade(m, k);
+
+                assert_eq!(terms.iter().map(|(a, i)| binomial(*a, *i)).sum::<u128>(), m);
+                assert!(terms
+                    .windows(2)
+                    .all(|t| t[0].0 > t[1].0 && t[0].1 == t[1].1 + 1));
+                assert!(terms.iter().all(|(a, i)| a >= i));
+            }
+        }
+
+        // Least shadow over every family of k-sets of 0..5
+        for k in 1..=4 {
+            let layer: Vec<Bits<5>> = Bits::<5>::layer(k).collect();
+            let mut least = vec![u128::MAX; layer.len() + 1];
+
+            for mask in 0_u32..1 << layer.len() {
+                let family: Vec<Bits<5>> = (0..layer.len())
+                    .filter(|j| mask >> j & 1 == 1)
+                    .map(|j| layer[j])
+                    .collect();
+                let size = shadow::shadow(&family).len() as u128;
+
+                least[family.len()] = least[family.len()].min(size);
+            }
+
+            for (m, size) in least.iter().enumerate() {
+                assert_eq!(shadow::kruskal_katona(m as u128, k), *size);
+
+                let colex: Vec<Bits<5>> = layer[..m].to_vec();
+                assert_eq!(shadow::shadow(&colex).len() as u128, *size);
+            }
+        }
+
+        let family = ["1100", "0110"].map(|x| Bits::<4>::from_str(x).unwrap());
+        let strings = |xs: Vec<Bits<4>>| xs.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(strings(shadow::shadow(&family)), ["1000", "0100", "0010"]);
+        assert_eq!(strings(shadow::shade(&family)), ["1110", "1101", "0111"]);
+
+        // Every f-vector of a down-set of subsets of 0..4, by brute force
+        let points: Vec<Bits<4>> = interval::Interval::<4>::cube().iter().collect();
+        let mut profiles = HashSet::new();
+
+        for mask in 0_u32..1 << points.len() {
+            let family: Vec<Bits<4>> = (0..points.len())
+                .filter(|j| mask >> j & 1 == 1)
+                .map(|j| points[j])
+                .collect();
+
+            if family
+                .iter()
+                .all(|x| x.lower_shadow().all(|y| family.contains(&y)))
+            {
+                let mut profile = [0_u128; 5];
+
+                for x in &family {
+                    profile[x.count_ones()] += 1;
+                }
+
+                profiles.insert(profile);
+            }
+        }
+
+        let mut candidates = vec![vec![]];
+
+        for k in 0..=4 {
+            candidates = candidates
+                .into_iter()
+                .flat_map(|prefix: Vec<u128>| {
+                    (0..=binomial(4, k)).map(move |f| [prefix.clone(), vec![f]].concat())
+                })
+                .collect();
+        }
+
+        assert_eq!(candidates.len(), 700);
+
+        for profile in candidates {
+            let profile: [u128; 5] = profile.try_into().unwrap();
+            let realisable = profiles.contains(&profile);
+
+            assert_eq!(shadow::is_down_set_profile(&profile, 4), realisable);
+
+            if let Some(family) = shadow::down_set_with_profile::<4>(&profile) {
+                assert!(family
+                    .iter()
+                    .all(|x| x.lower_shadow().all(|y| family.contains(&y))));
+                assert_eq!(family.len() as u128, profile.iter().sum::<u128>());
+            }
+        }
+    }
+
+    #[test]
+    fn test_family_operations() {
+        use family::Family;
+
+        const M: usize = 5;
+
+        let mut rng = StdRng::seed_from_u64(18);
+        let points: Vec<Bits<M>> = interval::Interval::<M>::cube().iter().collect();
+        let random_family = |rng: &mut StdRng| -> Family<M> {
+            let p = rng.gen_range(0.05..0.4);
+
+            points.iter().copied().filter(|_| rng.gen_bool(p)).collect()
+        };
+
+        for _ in 0..200 {
+            let f = random_family(&mut rng);
+            let g = random_family(&mut rng);
+            let members: Vec<Bits<M>> = f.iter().collect();
+            let below = |x: &Bits<M>| members.iter().any(|a| a <= x);
+            let above = |x: &Bits<M>| members.iter().any(|a| x <= a);
+
+            let minimal: Family<M> = members
+                .iter()
+                .copied()
+                .filter(|x| !members.iter().any(|a| a < x))
+                .collect();
+            let maximal: Family<M> = members
+                .iter()
+                .copied()
+                .filter(|x| !members.iter().any(|a| x < a))
+                .collect();
+
+            assert_eq!(f.minimal(), minimal);
+            assert_eq!(f.maximal(), maximal);
+            assert_eq!(f.is_antichain(), minimal.len() == f.len());
+            assert!(f.minimal().is_antichain());
+
+            let up: Vec<Bits<M>> = f.up_closure().collect();
+            let down: Vec<Bits<M>> = f.down_closure().collect();
+
+            assert_eq!(up.len(), up.iter().collect::<HashSet<_>>().len());
+            assert_eq!(down.len(), down.iter().collect::<HashSet<_>>().len());
+            assert_eq!(
+                up.into_iter().collect::<Family<M>>(),
+                points.iter().copied().filter(below).collect()
+            );
+            assert_eq!(
+                down.into_iter().collect::<Family<M>>(),
+                points.iter().copied().filter(above).collect()
+            );
+
+            for x in &points {
+                assert_eq!(f.is_below(x), below(x));
+                assert_eq!(f.is_above(x), above(x));
+
+                let (in_f, in_g) = (f.contains(x), g.contains(x));
+
+                assert_eq!(f.union(&g).contains(x), in_f || in_g);
+                assert_eq!(f.intersection(&g).contains(x), in_f && in_g);
+                assert_eq!(f.difference(&g).contains(x), in_f && !in_g);
+                assert_eq!(f.complement().contains(&!*x), in_f);
+            }
+
+            let transversals: Family<M> = points
+                .iter()
+                .copied()
+                .filter(|t| members.iter().all(|a| (*t & *a).or()))
+                .collect();
+
+            assert_eq!(f.blocker(), transversals.minimal());
+            assert_eq!(f.blocker().blocker(), f.minimal());
+        }
+
+        let empty = Family::<M>::new();
+        let bottom: Family<M> = [Bits::new(false)].into_iter().collect();
+
+        assert_eq!(empty.blocker(), bottom);
+        assert_eq!(bottom.blocker(), empty);
+        assert_eq!(empty.up_closure().count(), 0);
+    }
+
+    #[test]
+    fn test_antichains() {
+        use antichain::Antichain;
+        use family::Family;
+        use interval::Interval;
+
+        fn brute_force_maximal<const N: usize>(a: &Antichain<N>, interval: &Interval<N>) -> bool {
+            a.iter().all(|x| interval.contains(&x))
+                && interval
+                    .iter()
+                    .all(|x| a.iter().any(|y| x.partial_cmp(&y).is_some()))
+        }
+
+        fn check<const N: usize>(interval: Interval<N>, count: usize) {
+            let antichains: Vec<Antichain<N>> = interval.maximal_antichains().collect();
+            let distinct: HashSet<&Antichain<N>> = antichains.iter().collect();
+
+            assert_eq!(antichains.len(), count);
+            assert_eq!(distinct.len(), count);
+
+            for a in &antichains {
+                assert!(a.family().is_antichain());
+                assert!(a.is_maximal_in(&interval));
+                assert!(brute_force_maximal(a, &interval));
+                assert!(a.lym() <= 1.0 + 1e-9);
+            }
+        }
+
+        check(Interval::<0>::cube(), 1);
+        check(Interval::<1>::cube(), 2);
+        check(Interval::<2>::cube(), 3);
+        check(Interval::<3>::cube(), 7);
+        check(Interval::<4>::cube(), 29);
+        check(Interval::<5>::cube(), 376);
+        check(
+            Bits::<6>::from_str("010000")
+                .unwrap()
+                .interval(&Bits::from_str("011111").unwrap())
+                .unwrap(),
+            29,
+        );
+        assert_eq!(Antichain::<3>::maximal_antichains().count(), 7);
+
+        let mut rng = StdRng::seed_from_u64(19);
+        let interval = Bits::<5>::from_str("00100")
+            .unwrap()
+            .interval(&Bits::from_str("11101").unwrap())
+            .unwrap();
+
+        for _ in 0..300 {
+            let f: Family<5> = Interval::<5>::cube()
+                .iter()
+                .filter(|_| rng.gen_bool(0.15))
+                .collect();
+            let a = match Antichain::new(f.clone()) {
+                Ok(a) => a,
+                Err(_) => {
+                    assert!(!f.is_antichain());
+                    Antichain::new(f.minimal()).unwrap()
+                }
+            };
+
+            assert_eq!(a.is_maximal(), brute_force_maximal(&a, &Interval::cube()));
+            assert_eq!(
+                a.is_maximal_in(&interval),
+                brute_force_maximal(&a, &interval)
+            );
+        }
+
+        let middle = Antichain::<6>::try_from(Bits::<6>::layer(3).collect::<Vec<_>>()).unwrap();
+
+        assert_eq!(middle.len() as u128, Antichain::<6>::width());
+        assert!(approx_eq!(f64, middle.lym(), 1.0, ulps = 4));
+        assert!(middle.is_maximal());
+        assert!(!Antichain::<6>::default().is_maximal());
+        assert!(Antichain::<2>::try_from(vec![Bits::new(false), Bits::new(true)]).is_err());
+
+        let f = function::MonotoneFunction::<4>::new(
+            ["1100", "0110", "1110"]
+                .map(|x| Bits::from_str(x).unwrap())
+                .to_vec(),
+        );
+
+        assert_eq!(function::MonotoneFunction::from(f.antichain()), f);
+    }
+
+    #[test]
+    fn test_truth_tables() {
+        use function::MonotoneFunction;
+        use interval::Interval;
+        use truth::TruthTable;
+
+        fn check<const N: usize>(rng: &mut StdRng) {
+            let points: Vec<Bits<N>> = Interval::<N>::cube().iter().collect();
+            let random = |rng: &mut StdRng| -> Vec<bool> {
+                let p = rng.gen_range(0.0..1.0);
+
+                points.iter().map(|_| rng.gen_bool(p)).collect()
+            };
+
+            for _ in 0..50 {
+                let (xs, ys) = (random(rng), random(rng));
+                let f = TruthTable::<N>::from_fn(|x| xs[x.rank() as usize]);
+                let g = TruthTable::<N>::from_fn(|x| ys[x.rank() as usize]);
+                let up = f.up_closure();
+                let down = f.down_closure();
+
+                assert_eq!(f.count_ones(), xs.iter().filter(|x| **x).count() as u64);
+                assert_eq!(f.count_ones() + f.count_zeroes(), 1 << N);
+                assert_eq!((!&f).count_ones(), f.count_zeroes());
+
+                for (j, x) in points.iter().enumerate() {
+                    assert_eq!(f.call(*x), xs[j]);
+                    assert_eq!((&f & &g).call(*x), xs[j] && ys[j]);
+                    assert_eq!((&f | &g).call(*x), xs[j] || ys[j]);
+                    assert_eq!((!&f).call(*x), !xs[j]);
+                    assert_eq!(up.call(*x), points.iter().any(|y| y <= x && f.call(*y)));
+                    assert_eq!(down.call(*x), points.iter().any(|y| x <= y && f.call(*y)));
+                }
+
+                let violation = points.iter().find_map(|x| {
+                    x.horizon(false)
+                        .find(|y| f.call(*x) && !f.call(*y))
+                        .map(|y| (*x, y))
+                });
+
+                assert_eq!(f.is_monotone(), violation.is_none());
+
+                if let Some((x, y)) = f.monotonicity_violation() {
+                    assert!(f.call(x) && !f.call(y));
+                    assert_eq!(x.distance(&y).unwrap(), 1);
+                    assert!(x < y);
+                }
+
+                assert!(up.is_monotone());
+                assert!((!&down).is_monotone());
+
+                let monotone = up.minimal_true_points();
+
+                assert_eq!(TruthTable::from(&monotone), up);
+                assert_eq!(f.minimal_true_points(), monotone);
+                assert_eq!(monotone, MonotoneFunction::new(f.ones().collect()));
+            }
+
+            let mut t = TruthTable::<N>::new(false);
+
+            for x in &points {
+                t.set(*x, true);
+                assert!(t.call(*x));
+            }
+
+            assert_eq!(t, TruthTable::new(true));
+        }
+
+        let mut rng = StdRng::seed_from_u64(20);
+
+        check::<0>(&mut rng);
+        check::<3>(&mut rng);
+        check::<6>(&mut rng);
+        check::<8>(&mut rng);
+    }
+
+    #[test]
+    fn test_minimize() {
+        use minimize::minimize;
+
+        fn check<const N: usize>(rng: &mut StdRng, size: usize) {
+            let p = rng.gen_range(0.0..1.0);
+            let implicants: Vec<Bits<N>> = (0..size)
+                .map(|_| {
+                    let mut x = Bits::new(false);
+
+                    for i in 0..N {
+                        x.set(i, rng.gen_bool(p));
+                    }
+
+                    x
+                })
+                .collect();
+            let minimized = minimize(implicants.clone());
+            let dynamic = minimize(implicants.iter().map(DynBits::from).collect());
+            let expected: HashSet<Bits<N>> = implicants
+                .iter()
+                .filter(|x| !implicants.iter().any(|a| a < *x))
+                .copied()
+                .collect();
+
+            assert_eq!(HashSet::from_iter(minimized.minimal.clone()), expected);
+            assert_eq!(minimized.minimal.len(), expected.len());
+            assert_eq!(
+                dynamic.minimal,
+                Vec::from_iter(minimized.minimal.iter().map(DynBits::from))
+            );
+            assert_eq!(
+                minimized.minimal.len() + minimized.absorbed.len(),
+                implicants.len()
+            );
+            assert!(minimized
+                .minimal
+                .windows(2)
+                .all(|w| w[0].count_ones() <= w[1].count_ones()));
+
+            for (x, a) in &minimized.absorbed {
+                assert!(a <= x);
+                assert!(expected.contains(a));
+            }
+
+            let mut dropped: Vec<Bits<N>> = minimized.absorbed.iter().map(|(x, _)| *x).collect();
+            let mut rest = implicants;
+
+            for a in &minimized.minimal {
+                let k = rest.iter().position(|x| x == a).unwrap();
+
+                rest.swap_remove(k);
+            }
+
+            dropped.sort_by_key(|x| x.rank());
+            rest.sort_by_key(|x| x.rank());
+            assert_eq!(dropped, rest);
+        }
+
+        let mut rng = StdRng::seed_from_u64(21);
+
+        for size in [0, 1, 10, 100, 1000] {
+            for _ in 0..10 {
+                check::<0>(&mut rng, size);
+                check::<5>(&mut rng, size);
+                check::<20>(&mut rng, size);
+                check::<70>(&mut rng, size);
+            }
+        }
+
+        let implicants = ["10100", "10110", "00011"].map(|s| DynBits::from_str(s).unwrap());
+        let f = DynMonotoneFunction::new(5, implicants.to_vec()).unwrap();
+
+        assert_eq!(f.implicants().count(), 2);
+        assert!(f.call(&DynBits::from_str("10101").unwrap()));
+        assert!(DynMonotoneFunction::new(4, implicants.to_vec()).is_err());
+    }
+
+    #[test]
+    fn test_dual() {
+        use function::MonotoneFunction;
+        use interval::Interval;
+        use learner::{Learner, LearnerConfig};
+        use truth::TruthTable;
+
+        fn check<const N: usize>(rng: &mut StdRng) {
+            let points: Vec<Bits<N>> = Interval::<N>::cube().iter().collect();
+
+            for _ in 0..50 {
+                let p = rng.gen_range(0.0..1.0);
+                let size = rng.gen_range(0..3 * N + 2);
+                let f = random_function::<N>(size, p, rng);
+                let dual = f.dual();
+                let table = TruthTable::from(&f);
+
+                assert_eq!(dual, f.dual_berge());
+                assert_eq!(dual.dual(), f);
+
+                for x in &points {
+                    assert_eq!(dual.call(*x), !f.call(!*x));
+                    assert_eq!(f.cnf().iter().all(|c| (c & *x).or()), f.call(*x));
+                }
+
+                let maximal: HashSet<Bits<N>> = points
+                    .iter()
+                    .filter(|x| !table.call(**x) && x.horizon(false).all(|y| table.call(y)))
+                    .copied()
+                    .collect();
+
+                assert_eq!(HashSet::from_iter(f.maximal_false_points().iter()), maximal);
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(22);
+
+        check::<0>(&mut rng);
+        check::<1>(&mut rng);
+        check::<4>(&mut rng);
+        check::<7>(&mut rng);
+
+        let oracle = MonotoneFunction::<6>::new(vec![Bits::from_str("110000").unwrap()]);
+        let mut learner = Learner::with_config(oracle.clone(), LearnerConfig::default().seed(22));
+
+        learner.iterate();
+        assert!(!learner.is_complete());
+        assert!(learner.hypothesis().implicants().all(|a| oracle.call(a)));
+    }
+
+    #[test]
+    fn test_duality_testing() {
+        use dual::{duality_witness_a, duality_witness_b};
+        use function::MonotoneFunction;
+        use interval::Interval;
+        use truth::TruthTable;
+
+        fn check<const N: usize>(f: &MonotoneFunction<N>, g: &MonotoneFunction<N>) {
+            let dual = Interval::<N>::cube()
+                .iter()
+                .all(|x| f.call(x) != g.call(!x));
+
+            for x in [duality_witness_a(f, g), duality_witness_b(f, g)] {
+                match x {
+                    Some(x) => assert_eq!(f.call(x), g.call(!x)),
+                    None => assert!(dual, "{f:?} {g:?}"),
+                }
+
+                assert_eq!(x.is_none(), dual);
+            }
+
+            assert_eq!(f.is_dual_of(g), dual);
+        }
+
+        // Every pair of monotone functions of up to four variables
+        fn exhaustive<const N: usize>() {
+            let functions: Vec<MonotoneFunction<N>> = (0..1_u64 << (1 << N))
+                .map(|t| TruthTable::<N>::from_fn(|x| t >> x.rank() & 1 == 1))
+                .filter(|t| t.is_monotone())
+                .map(|t| t.minimal_true_points())
+                .collect();
+
+            for f in &functions {
+                for g in &functions {
+                    check(f, g);
+                }
+            }
+        }
+
+        exhaustive::<0>();
+        exhaustive::<1>();
+        exhaustive::<2>();
+        exhaustive::<3>();
+        exhaustive::<4>();
+
+        // Larger duals and their perturbations, which are rarely dual
+        fn random<const N: usize>(rng: &mut StdRng) {
+            for _ in 0..50 {
+                let p = rng.gen_range(0.1..0.6);
+                let size = rng.gen_range(1..4 * N);
+                let f = random_function::<N>(size, p, rng);
+                let g = f.dual();
+
+                check(&f, &g);
+                check(&g, &f);
+
+                let mut implicants: Vec<Bits<N>> = g.implicants().collect();
+
+                if !implicants.is_empty() {
+                    let k = rng.gen_range(0..implicants.len());
+
+                    implicants.swap_remove(k);
+                }
+
+                check(&f, &MonotoneFunction::new(implicants.clone()));
+
+                implicants.push(sample::biased(&Interval::cube(), 0.5, rng));
+                check(&f, &MonotoneFunction::new(implicants));
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(23);
+
+        random::<8>(&mut rng);
+        random::<12>(&mut rng);
+    }
+
+    #[test]
+    fn test_function_algebra() {
+        use function::MonotoneFunction;
+        use interval::Interval;
+        use truth::TruthTable;
+
+        const N: usize = 6;
+
+        let mut rng = StdRng::seed_from_u64(24);
+        let points: Vec<Bits<N>> = Interval::<N>::cube().iter().collect();
+        let random = |rng: &mut StdRng| {
+            let p = rng.gen_range(0.2..0.7);
+            let size = rng.gen_range(0..8);
+
+            random_function::<N>(size, p, rng)
+        };
+        let minimal = |f: &MonotoneFunction<N>| {
+            assert_eq!(*f, TruthTable::from(f).minimal_true_points());
+        };
+
+        assert!(points
+            .iter()
+            .all(|x| MonotoneFunction::constant(true).call(*x)));
+        assert!(points
+            .iter()
+            .all(|x| !MonotoneFunction::constant(false).call(*x)));
+
+        for _ in 0..100 {
+            let f = random(&mut rng);
+            let g = random(&mut rng);
+            let and = &f & &g;
+            let or = f.clone() | g.clone();
+
+            minimal(&and);
+            minimal(&or);
+            assert_eq!(and, f.and(&g));
+            assert_eq!(or, f.or(&g));
+
+            let i = rng.gen_range(0..N);
+            let substituted = f.substitute(i, &g);
+
+            minimal(&substituted);
+
+            let functions: Vec<MonotoneFunction<N>> = (0..4).map(|_| random(&mut rng)).collect();
+            let k = rng.gen_range(0..6);
+            let threshold = MonotoneFunction::threshold(&functions, k);
+
+            minimal(&threshold);
+
+            let inner: [MonotoneFunction<N>; N] = std::array::from_fn(|_| random(&mut rng));
+            let composed = f.compose(&inner);
+
+            minimal(&composed);
+
+            for x in &points {
+                let mut y = *x;
+
+                y.set(i, g.call(*x));
+
+                let z = inner
+                    .iter()
+                    .enumerate()
+                    .fold(Bits::new(false), |mut z, (j, h)| {
+                        z.set(j, h.call(*x));
+                        z
+                    });
+
+                assert_eq!(and.call(*x), f.call(*x) && g.call(*x));
+                assert_eq!(or.call(*x), f.call(*x) || g.call(*x));
+                assert_eq!(substituted.call(*x), f.call(y));
+                assert_eq!(
+                    threshold.call(*x),
+                    functions.iter().filter(|h| h.call(*x)).count() >= k
+                );
+                assert_eq!(composed.call(*x), f.call(z));
+            }
+        }
+
+        let majority =
+            MonotoneFunction::<3>::threshold(&[0, 1, 2].map(MonotoneFunction::variable), 2);
+
+        assert_eq!(majority.dual(), majority);
+        assert_eq!(majority.implicants().count(), 3);
+    }
+
+    #[test]
+    fn test_compiled_evaluation() {
+        use function::MonotoneFunction;
+        use interval::Interval;
+
+        fn check<const N: usize>(rng: &mut StdRng) {
+            for _ in 0..50 {
+                let p = rng.gen_range(0.0..0.5);
+                let size = rng.gen_range(0..20);
+                let f = random_function::<N>(size, p, rng);
+                let compiled = f.compile();
+                let q = rng.gen_range(0.0..1.0);
+                let xs: Vec<Bits<N>> = (0..rng.gen_range(0..200))
+                    .map(|_| sample::biased(&Interval::cube(), q, rng))
+                    .collect();
+                let expected: Vec<bool> = xs.iter().map(|x| f.call(*x)).collect();
+
+                assert_eq!(
+                    xs.iter().map(|x| compiled.call(*x)).collect::<Vec<_>>(),
+                    expected
+                );
+                assert_eq!(compiled.call_batch(&xs), expected);
+            }
+
+            for value in [false, true] {
+                let compiled = MonotoneFunction::<N>::constant(value).compile();
+                let x = sample::biased(&Interval::cube(), 0.5, rng);
+
+                assert_eq!(compiled.call(x), value);
+                assert_eq!(compiled.call_batch(&[x; 70]), vec![value; 70]);
+                assert_eq!(
+                    compiled.call_sliced(&[0; N]),
+                    if value { u64::MAX } else { 0 }
+                );
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(25);
+
+        check::<0>(&mut rng);
+        check::<7>(&mut rng);
+        check::<64>(&mut rng);
+        check::<100>(&mut rng);
+    }
+}
+
+/// Evaluation benchmarks, run with `cargo +nightly bench --features bench`.
+#[cfg(all(test, feature = "bench"))]
+mod benches {
+    extern crate test;
+
+    use super::*;
+    use bits::Bits;
+    use function::MonotoneFunction;
+    use rand::{rngs::StdRng, SeedableRng};
+    use test::Bencher;
+
+    const N: usize = 64;
+
+    /// A function of `size` implicants and a thousand points, at densities
+    /// leaving roughly half the points true.
+    fn setup(size: usize, p: f64) -> (MonotoneFunction<N>, Vec<Bits<N>>) {
+        let mut rng = StdRng::seed_from_u64(25);
+        let f = tests::random_function(size, p, &mut rng);
+        let xs = (0..1_000)
+            .map(|_| sample::biased(&interval::Interval::cube(), 0.3, &mut rng))
+            .collect();
+
+        (f, xs)
+    }
+
+    fn call(b: &mut Bencher, size: usize, p: f64) {
+        let (f, xs) = setup(size, p);
+
+        b.iter(|| xs.iter().filter(|x| f.call(**x)).count());
+    }
+
+    fn compile(b: &mut Bencher, size: usize, p: f64) {
+        let (f, _) = setup(size, p);
+
+        b.iter(|| f.compile());
+    }
+
+    fn compiled_call(b: &mut Bencher, size: usize, p: f64) {
+        let (f, xs) = setup(size, p);
+        let compiled = f.compile();
+
+        b.iter(|| xs.iter().filter(|x| compiled.call(**x)).count());
+    }
+
+    fn call_batch(b: &mut Bencher, size: usize, p: f64) {
+        let (f, xs) = setup(size, p);
+        let compiled = f.compile();
+
+        b.iter(|| compiled.call_batch(&xs));
+    }
+
+    #[bench]
+    fn bench_call_small(b: &mut Bencher) {
+        call(b, 100, 0.1);
+    }
+
+    #[bench]
+    fn bench_call_large(b: &mut Bencher) {
+        call(b, 20_000, 0.2);
+    }
+
+    #[bench]
+    fn bench_compile_small(b: &mut Bencher) {
+        compile(b, 100, 0.1);
+    }
+
+    #[bench]
+    fn bench_compile_large(b: &mut Bencher) {
+        compile(b, 20_000, 0.2);
+    }
+
+    #[bench]
+    fn bench_compiled_call_small(b: &mut Bencher) {
+        compiled_call(b, 100, 0.1);
+    }
+
+    #[bench]
+    fn bench_compiled_call_large(b: &mut Bencher) {
+        compiled_call(b, 20_000, 0.2);
+    }
+
+    #[bench]
+    fn bench_call_batch_small(b: &mut Bencher) {
+        call_batch(b, 100, 0.1);
+    }
+
+    #[bench]
+    fn bench_call_batch_large(b: &mut Bencher) {
+        call_batch(b, 20_000, 0.2);
     }
 }
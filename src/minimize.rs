@@ -0,0 +1,192 @@
+#![allow(dead_code)]
+
+use crate::packed::{self, next_one, Packed};
+
+/// Outcome of `minimize`: the minimal implicants, lightest first, and each
+/// dropped implicant paired with a kept one lying at or below it.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Minimized<B> {
+    pub minimal: Vec<B>,
+    pub absorbed: Vec<(B, B)>,
+}
+
+/// Members a node holds before it is split among its children.
+const BUCKET: usize = 16;
+
+/// Set-trie (Savnik) over the kept implicants, each filed under the path of
+/// its set coordinates in increasing order. Implicants wait in a bucket at
+/// the deepest node created so far, and a full bucket is split one level
+/// down, so paths are only as long as they need to be. Every node also
+/// holds the intersection of the implicants below it, which must lie inside
+/// any set that one of them lies inside.
+#[derive(Debug)]
+struct SetTrie<B> {
+    sets: Vec<B>,
+    nodes: Vec<Node<B>>,
+    // Every coordinate set, the intersection of no implicants
+    top: B,
+}
+
+#[derive(Debug)]
+struct Node<B> {
+    // Last coordinate on the path to the node, `None` at the root
+    coordinate: Option<usize>,
+    // Children as (coordinate, node) pairs in increasing coordinate order
+    children: Vec<(usize, usize)>,
+    members: Vec<usize>,
+    common: B,
+    split: bool,
+}
+
+impl<B: Packed> SetTrie<B> {
+    fn new(top: B) -> Self {
+        let mut trie = Self {
+            sets: Vec::new(),
+            nodes: Vec::new(),
+            top,
+        };
+
+        trie.nodes.push(trie.node(None));
+        trie
+    }
+
+    fn node(&self, coordinate: Option<usize>) -> Node<B> {
+        Node {
+            coordinate,
+            children: Vec::new(),
+            members: Vec::new(),
+            common: self.top.clone(),
+            split: false,
+        }
+    }
+
+    /// The first set coordinate of `x` past the path to `node`.
+    fn next_coordinate(&self, node: usize, x: &B) -> Option<usize> {
+        let from = self.nodes[node].coordinate.map_or(0, |c| c + 1);
+
+        next_one(&x.limbs(), x.len(), from)
+    }
+
+    fn child(&mut self, node: usize, i: usize) -> usize {
+        let children = &self.nodes[node].children;
+
+        match children.binary_search_by_key(&i, |(j, _)| *j) {
+            Ok(k) => children[k].1,
+            Err(k) => {
+                let child = self.nodes.len();
+
+                self.nodes[node].children.insert(k, (i, child));
+                self.nodes.push(self.node(Some(i)));
+                child
+            }
+        }
+    }
+
+    fn insert(&mut self, x: B) {
+        let member = self.sets.len();
+        let mut node = 0;
+
+        loop {
+            intersect(&mut self.nodes[node].common, &x);
+
+            let next = self.next_coordinate(node, &x);
+
+            match next {
+                Some(i) if self.nodes[node].split => node = self.child(node, i),
+                _ => {
+                    self.sets.push(x);
+                    self.nodes[node].members.push(member);
+
+                    if !self.nodes[node].split && self.nodes[node].members.len() > BUCKET {
+                        self.split(node);
+                    }
+
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Moves every member of `node` whose path continues into a child.
+    fn split(&mut self, node: usize) {
+        let members = std::mem::take(&mut self.nodes[node].members);
+
+        self.nodes[node].split = true;
+
+        for member in members {
+            match self.next_coordinate(node, &self.sets[member]) {
+                Some(i) => {
+                    let child = self.child(node, i);
+                    let (nodes, sets) = (&mut self.nodes, &self.sets);
+
+                    intersect(&mut nodes[child].common, &sets[member]);
+                    self.nodes[child].members.push(member);
+                }
+                None => self.nodes[node].members.push(member),
+            }
+        }
+
+        for k in 0..self.nodes[node].children.len() {
+            let child = self.nodes[node].children[k].1;
+
+            if self.nodes[child].members.len() > BUCKET {
+                self.split(child);
+            }
+        }
+    }
+
+    /// Some member lying inside `x`.
+    fn find_subset(&self, node: usize, x: &B) -> Option<usize> {
+        let node = &self.nodes[node];
+
+        if !packed::is_subset(&node.common.limbs(), &x.limbs()) {
+            return None;
+        }
+
+        node.members
+            .iter()
+            .copied()
+            .find(|member| packed::is_subset(&self.sets[*member].limbs(), &x.limbs()))
+            .or_else(|| {
+                node.children
+                    .iter()
+                    .filter(|(i, _)| packed::get(&x.limbs(), *i))
+                    .find_map(|(_, child)| self.find_subset(*child, x))
+            })
+    }
+}
+
+fn intersect<B: Packed>(a: &mut B, b: &B) {
+    for (x, y) in a.limbs_mut().iter_mut().zip(b.limbs().iter()) {
+        *x &= y;
+    }
+}
+
+/// Drops every implicant lying above another, duplicates included. The rest
+/// are visited by increasing weight, so each need only be looked up among
+/// the minimal ones already kept. The implicants must all have the same
+/// length.
+pub fn minimize<B: Packed>(mut implicants: Vec<B>) -> Minimized<B> {
+    let Some(first) = implicants.first() else {
+        return Minimized {
+            minimal: Vec::new(),
+            absorbed: Vec::new(),
+        };
+    };
+    let mut trie = SetTrie::new(packed::filled(first, true));
+    let mut absorbed = Vec::new();
+
+    implicants.sort_by_key(|x| packed::count_ones(&x.limbs()));
+
+    for x in implicants {
+        match trie.find_subset(0, &x) {
+            Some(member) => absorbed.push((x, trie.sets[member].clone())),
+            None => trie.insert(x),
+        }
+    }
+
+    Minimized {
+        minimal: trie.sets,
+        absorbed,
+    }
+}
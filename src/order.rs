@@ -0,0 +1,130 @@
+#![allow(dead_code)]
+
+use std::{cmp::Ordering, fmt};
+
+use crate::{bits::Bits, dynbits::DynBits, packed::Packed};
+
+/// Total orders on bit vectors of equal length, complementing the subset
+/// `PartialOrd`. Every one of them is a linear extension of it.
+pub trait TotalOrd {
+    /// Compares as binary strings, index 0 being most significant.
+    fn lex_cmp(&self, other: &Self) -> Ordering;
+
+    /// Compares by the highest index at which the two differ.
+    fn colex_cmp(&self, other: &Self) -> Ordering;
+
+    /// Compares by number of ones, then colexicographically.
+    fn graded_cmp(&self, other: &Self) -> Ordering;
+}
+
+fn lex_cmp_limbs(a: &[u64], b: &[u64]) -> Ordering {
+    for (x, y) in a.iter().zip(b.iter()) {
+        let diff = x ^ y;
+
+        if diff != 0 {
+            let i = diff.trailing_zeros();
+
+            return ((x >> i) & 1).cmp(&((y >> i) & 1));
+        }
+    }
+
+    Ordering::Equal
+}
+
+fn colex_cmp_limbs(a: &[u64], b: &[u64]) -> Ordering {
+    for (x, y) in a.iter().zip(b.iter()).rev() {
+        let diff = x ^ y;
+
+        if diff != 0 {
+            let i = 63 - diff.leading_zeros();
+
+            return ((x >> i) & 1).cmp(&((y >> i) & 1));
+        }
+    }
+
+    Ordering::Equal
+}
+
+impl<const N: usize> TotalOrd for Bits<N> {
+    fn lex_cmp(&self, other: &Self) -> Ordering {
+        lex_cmp_limbs(&self.limbs(), &other.limbs())
+    }
+
+    fn colex_cmp(&self, other: &Self) -> Ordering {
+        colex_cmp_limbs(&self.limbs(), &other.limbs())
+    }
+
+    fn graded_cmp(&self, other: &Self) -> Ordering {
+        self.count_ones()
+            .cmp(&other.count_ones())
+            .then_with(|| self.colex_cmp(other))
+    }
+}
+
+impl TotalOrd for DynBits {
+    fn lex_cmp(&self, other: &Self) -> Ordering {
+        assert_eq!(self.len(), other.len(), "DynBits length mismatch");
+
+        lex_cmp_limbs(&self.limbs(), &other.limbs())
+    }
+
+    fn colex_cmp(&self, other: &Self) -> Ordering {
+        assert_eq!(self.len(), other.len(), "DynBits length mismatch");
+
+        colex_cmp_limbs(&self.limbs(), &other.limbs())
+    }
+
+    fn graded_cmp(&self, other: &Self) -> Ordering {
+        self.count_ones()
+            .cmp(&other.count_ones())
+            .then_with(|| self.colex_cmp(other))
+    }
+}
+
+macro_rules! total_order {
+    ($(#[$meta:meta])* $name:ident, $cmp:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Eq, Hash, PartialEq)]
+        pub struct $name<T>(pub T);
+
+        impl<T: Eq + TotalOrd> Ord for $name<T> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.$cmp(&other.0)
+            }
+        }
+
+        impl<T: Eq + TotalOrd> PartialOrd for $name<T> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<T: fmt::Debug> fmt::Debug for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.0, f)
+            }
+        }
+
+        impl<T: fmt::Display> fmt::Display for $name<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+total_order!(
+    /// Orders its contents by `TotalOrd::lex_cmp`.
+    Lex,
+    lex_cmp
+);
+total_order!(
+    /// Orders its contents by `TotalOrd::colex_cmp`.
+    Colex,
+    colex_cmp
+);
+total_order!(
+    /// Orders its contents by `TotalOrd::graded_cmp`.
+    Graded,
+    graded_cmp
+);
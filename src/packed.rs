@@ -0,0 +1,1075 @@
+#![allow(dead_code)]
+
+use std::{borrow::Cow, cmp::Ordering, fmt, iter::FusedIterator};
+
+use crate::{
+    bits::IncomparableError,
+    rank::subset_unrank,
+    util::{binomial, checked_binomial, checked_factorial},
+};
+
+/// Bit vector stored as `u64` limbs, bit `i` at bit `i % 64` of limb
+/// `i / 64`, with the bits past `len` always clear. Both `Bits<N>` and
+/// `DynBits` are, and the algorithms below are written once against it.
+pub trait Packed: Clone + fmt::Debug + PartialOrd {
+    fn len(&self) -> usize;
+
+    /// Limbs for reading, with bit `i` at bit `i % 64` of limb `i / 64`.
+    fn limbs(&self) -> Cow<'_, [u64]>;
+
+    /// Limbs for writing, which must leave the bits past `len` clear.
+    fn limbs_mut(&mut self) -> &mut [u64];
+}
+
+pub(crate) fn get(limbs: &[u64], i: usize) -> bool {
+    (limbs[i / 64] >> (i % 64)) & 1 == 1
+}
+
+pub(crate) fn set(limbs: &mut [u64], i: usize, value: bool) {
+    if value {
+        limbs[i / 64] |= 1 << (i % 64);
+    } else {
+        limbs[i / 64] &= !(1 << (i % 64));
+    }
+}
+
+pub(crate) fn count_ones(limbs: &[u64]) -> usize {
+    limbs.iter().map(|limb| limb.count_ones() as usize).sum()
+}
+
+/// Whether every bit set in `a` is set in `b`.
+pub(crate) fn is_subset(a: &[u64], b: &[u64]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x & !y == 0)
+}
+
+/// Clears the bits of the last limb past `len`.
+pub(crate) fn trim(limbs: &mut [u64], len: usize) {
+    if !len.is_multiple_of(64) {
+        if let Some(last) = limbs.last_mut() {
+            *last &= (1 << (len % 64)) - 1;
+        }
+    }
+}
+
+/// Index of the first clear bit at or after `from` among the first `len` bits
+/// of `limbs`.
+pub(crate) fn next_zero(limbs: &[u64], len: usize, from: usize) -> Option<usize> {
+    let mut cursor = from;
+
+    while cursor < len {
+        let (w, b) = (cursor / 64, cursor % 64);
+        let zeroes = !limbs[w] & (u64::MAX << b);
+
+        if zeroes != 0 {
+            let i = w * 64 + zeroes.trailing_zeros() as usize;
+
+            return if i < len { Some(i) } else { None };
+        }
+
+        cursor = (w + 1) * 64;
+    }
+
+    None
+}
+
+/// Index of the first set bit at or after `from` among the first `len` bits
+/// of `limbs`.
+pub(crate) fn next_one(limbs: &[u64], len: usize, from: usize) -> Option<usize> {
+    let mut cursor = from;
+
+    while cursor < len {
+        let (w, b) = (cursor / 64, cursor % 64);
+        let ones = limbs[w] & (u64::MAX << b);
+
+        if ones != 0 {
+            let i = w * 64 + ones.trailing_zeros() as usize;
+
+            return if i < len { Some(i) } else { None };
+        }
+
+        cursor = (w + 1) * 64;
+    }
+
+    None
+}
+
+/// Index of the last clear bit before `below` in `limbs`.
+pub(crate) fn previous_zero(limbs: &[u64], below: usize) -> Option<usize> {
+    let mut cursor = below;
+
+    while cursor > 0 {
+        let w = (cursor - 1) / 64;
+        let b = cursor - w * 64;
+        let mask = if b == 64 { u64::MAX } else { (1 << b) - 1 };
+        let zeroes = !limbs[w] & mask;
+
+        if zeroes != 0 {
+            return Some(w * 64 + 63 - zeroes.leading_zeros() as usize);
+        }
+
+        cursor = w * 64;
+    }
+
+    None
+}
+
+/// Moves every bit of `limbs` `rhs` places towards index 0.
+pub(crate) fn shift_limbs_down(limbs: &mut [u64], rhs: usize) {
+    let (skip, offset) = (rhs / 64, rhs % 64);
+    let words = limbs.len();
+
+    for w in 0..words {
+        limbs[w] = if w + skip < words {
+            limbs[w + skip] >> offset
+        } else {
+            0
+        };
+
+        if offset != 0 && w + skip + 1 < words {
+            limbs[w] |= limbs[w + skip + 1] << (64 - offset);
+        }
+    }
+}
+
+/// Moves every bit of `limbs` `rhs` places away from index 0.
+pub(crate) fn shift_limbs_up(limbs: &mut [u64], rhs: usize) {
+    let (skip, offset) = (rhs / 64, rhs % 64);
+
+    for w in (0..limbs.len()).rev() {
+        limbs[w] = if w >= skip {
+            limbs[w - skip] << offset
+        } else {
+            0
+        };
+
+        if offset != 0 && w > skip {
+            limbs[w] |= limbs[w - skip - 1] >> (64 - offset);
+        }
+    }
+}
+
+/// Hint for `remaining` items, exact when it fits in a `usize`.
+fn size_hint(remaining: u128) -> (usize, Option<usize>) {
+    match usize::try_from(remaining) {
+        Ok(remaining) => (remaining, Some(remaining)),
+        Err(_) => (usize::MAX, None),
+    }
+}
+
+fn flip<B: Packed>(x: &mut B, i: usize) {
+    let value = get(&x.limbs(), i);
+
+    set(x.limbs_mut(), i, !value);
+}
+
+/// `x` with every bit set to `value`.
+pub(crate) fn filled<B: Packed>(x: &B, value: bool) -> B {
+    let mut y = x.clone();
+    let len = y.len();
+
+    y.limbs_mut().fill(if value { u64::MAX } else { 0 });
+    trim(y.limbs_mut(), len);
+    y
+}
+
+pub(crate) fn complement<B: Packed>(x: &B) -> B {
+    let mut y = x.clone();
+    let len = y.len();
+
+    for limb in y.limbs_mut() {
+        *limb = !*limb;
+    }
+
+    trim(y.limbs_mut(), len);
+    y
+}
+
+/// Bottom of the interval spanned by `start` and `end`, its free
+/// coordinates in ascending order, and whether `start` is its top.
+pub(crate) fn span<B: Packed>(
+    start: &B,
+    end: &B,
+) -> Result<(B, Vec<usize>, bool), IncomparableError> {
+    let descending = match start.partial_cmp(end) {
+        None => return Err(IncomparableError),
+        Some(ordering) => ordering == Ordering::Greater,
+    };
+    let origin = if descending { end } else { start };
+
+    Ok((origin.clone(), differences(start, end), descending))
+}
+
+/// Coordinates where `a` and `b` differ, in ascending order.
+fn differences<B: Packed>(a: &B, b: &B) -> Vec<usize> {
+    let mut free = Vec::new();
+
+    for (w, (x, y)) in a.limbs().iter().zip(b.limbs().iter()).enumerate() {
+        let mut diff = x ^ y;
+
+        while diff != 0 {
+            free.push(w * 64 + diff.trailing_zeros() as usize);
+            diff &= diff - 1;
+        }
+    }
+
+    free
+}
+
+#[derive(Clone, Debug)]
+pub struct Zeroes<B> {
+    bits: B,
+    front: usize,
+    back: usize,
+    remaining: usize,
+}
+
+impl<B: Packed> Zeroes<B> {
+    pub(crate) fn new(bits: &B) -> Self {
+        Self {
+            bits: bits.clone(),
+            front: 0,
+            back: bits.len(),
+            remaining: bits.len() - count_ones(&bits.limbs()),
+        }
+    }
+}
+
+impl<B: Packed> Iterator for Zeroes<B> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let next = next_zero(&self.bits.limbs(), self.bits.len(), self.front)?;
+
+        self.front = next + 1;
+        self.remaining -= 1;
+
+        Some(next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<B: Packed> DoubleEndedIterator for Zeroes<B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let next = previous_zero(&self.bits.limbs(), self.back)?;
+
+        self.back = next;
+        self.remaining -= 1;
+
+        Some(next)
+    }
+}
+
+impl<B: Packed> ExactSizeIterator for Zeroes<B> {}
+
+impl<B: Packed> FusedIterator for Zeroes<B> {}
+
+#[derive(Clone, Debug)]
+pub struct Ones<B> {
+    inner: Zeroes<B>,
+}
+
+impl<B: Packed> Ones<B> {
+    pub(crate) fn new(bits: &B) -> Self {
+        Self {
+            inner: Zeroes::new(&complement(bits)),
+        }
+    }
+}
+
+impl<B: Packed> Iterator for Ones<B> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<B: Packed> DoubleEndedIterator for Ones<B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<B: Packed> ExactSizeIterator for Ones<B> {}
+
+impl<B: Packed> FusedIterator for Ones<B> {}
+
+/// Every point above the origin, level by level.
+#[derive(Clone, Debug)]
+pub struct UpperShadow<B> {
+    inner: Converge<B>,
+}
+
+impl<B: Packed> UpperShadow<B> {
+    pub(crate) fn new(origin: &B) -> Self {
+        Self {
+            inner: Converge::new(origin, &filled(origin, true)).unwrap(),
+        }
+    }
+
+    /// Number of points, including those already yielded, or `None` if that
+    /// overflows a `u128`.
+    pub fn total(&self) -> Option<u128> {
+        self.inner.total()
+    }
+}
+
+impl<B: Packed> Iterator for UpperShadow<B> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, next)| next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<B: Packed> DoubleEndedIterator for UpperShadow<B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, next)| next)
+    }
+}
+
+impl<B: Packed> FusedIterator for UpperShadow<B> {}
+
+/// Every point below the origin, level by level.
+#[derive(Clone, Debug)]
+pub struct LowerShadow<B> {
+    inner: Converge<B>,
+}
+
+impl<B: Packed> LowerShadow<B> {
+    pub(crate) fn new(origin: &B) -> Self {
+        Self {
+            inner: Converge::new(origin, &filled(origin, false)).unwrap(),
+        }
+    }
+
+    /// Number of points, including those already yielded, or `None` if that
+    /// overflows a `u128`.
+    pub fn total(&self) -> Option<u128> {
+        self.inner.total()
+    }
+}
+
+impl<B: Packed> Iterator for LowerShadow<B> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, next)| next)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<B: Packed> DoubleEndedIterator for LowerShadow<B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, next)| next)
+    }
+}
+
+impl<B: Packed> FusedIterator for LowerShadow<B> {}
+
+/// The middle layer of an interval, or the two middle layers when its
+/// dimension is odd.
+#[derive(Clone, Debug)]
+pub struct Midpoints<B> {
+    lower: Layer<B>,
+    upper: Option<Layer<B>>,
+}
+
+impl<B: Packed> Midpoints<B> {
+    pub(crate) fn new(start: &B, end: &B) -> Result<Self, IncomparableError> {
+        let (origin, free, _) = span(start, end)?;
+
+        Ok(Self::build(origin, free))
+    }
+
+    /// Midpoints from `lo` to `hi`, which must lie below it.
+    pub(crate) fn between(lo: &B, hi: &B) -> Self {
+        Self::build(lo.clone(), differences(lo, hi))
+    }
+
+    fn build(origin: B, free: Vec<usize>) -> Self {
+        let size = free.len();
+        let upper = if size.is_multiple_of(2) {
+            None
+        } else {
+            Some(Layer::build(origin.clone(), free.clone(), size / 2 + 1))
+        };
+
+        Self {
+            lower: Layer::build(origin, free, size / 2),
+            upper,
+        }
+    }
+
+    /// Number of midpoints, including those already yielded, or `None` if
+    /// that overflows a `u128`.
+    pub fn total(&self) -> Option<u128> {
+        match &self.upper {
+            None => self.lower.total(),
+            Some(upper) => self.lower.total()?.checked_add(upper.total()?),
+        }
+    }
+
+    /// The midpoint at `index`, lower layer first, independent of iteration
+    /// progress.
+    pub fn get(&self, index: u128) -> Option<B> {
+        match self.lower.total() {
+            Some(total) if index >= total => self.upper.as_ref()?.get(index - total),
+            _ => self.lower.get(index),
+        }
+    }
+}
+
+impl<B: Packed> Iterator for Midpoints<B> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lower
+            .next()
+            .or_else(|| self.upper.as_mut().and_then(|upper| upper.next()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = match &self.upper {
+            None => self.lower.remaining(),
+            Some(upper) => self
+                .lower
+                .remaining()
+                .zip(upper.remaining())
+                .and_then(|(lower, upper)| lower.checked_add(upper)),
+        };
+
+        remaining.map_or((usize::MAX, None), size_hint)
+    }
+}
+
+impl<B: Packed> DoubleEndedIterator for Midpoints<B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.upper
+            .as_mut()
+            .and_then(|upper| upper.next_back())
+            .or_else(|| self.lower.next_back())
+    }
+}
+
+impl<B: Packed> FusedIterator for Midpoints<B> {}
+
+#[derive(Clone, Debug)]
+enum HorizonIndices<B> {
+    Upper(Zeroes<B>),
+    Lower(Ones<B>),
+}
+
+#[derive(Clone, Debug)]
+pub struct Horizon<B> {
+    origin: B,
+    indices: HorizonIndices<B>,
+    lower: bool,
+}
+
+impl<B: Packed> Horizon<B> {
+    pub(crate) fn new(origin: &B, lower: bool) -> Self {
+        let indices = if lower {
+            HorizonIndices::Lower(Ones::new(origin))
+        } else {
+            HorizonIndices::Upper(Zeroes::new(origin))
+        };
+
+        Self {
+            origin: origin.clone(),
+            indices,
+            lower,
+        }
+    }
+
+    fn step(&self, i: usize) -> B {
+        let mut next = self.origin.clone();
+
+        set(next.limbs_mut(), i, !self.lower);
+        next
+    }
+}
+
+impl<B: Packed> Iterator for Horizon<B> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let maybe_index = match self.indices {
+            HorizonIndices::Upper(ref mut zeroes) => zeroes.next(),
+            HorizonIndices::Lower(ref mut ones) => ones.next(),
+        };
+
+        maybe_index.map(|i| self.step(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.indices {
+            HorizonIndices::Upper(ref zeroes) => zeroes.size_hint(),
+            HorizonIndices::Lower(ref ones) => ones.size_hint(),
+        }
+    }
+}
+
+impl<B: Packed> DoubleEndedIterator for Horizon<B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let maybe_index = match self.indices {
+            HorizonIndices::Upper(ref mut zeroes) => zeroes.next_back(),
+            HorizonIndices::Lower(ref mut ones) => ones.next_back(),
+        };
+
+        maybe_index.map(|i| self.step(i))
+    }
+}
+
+impl<B: Packed> ExactSizeIterator for Horizon<B> {}
+
+impl<B: Packed> FusedIterator for Horizon<B> {}
+
+/// Every point of the interval between `start` and `end`, one level at a
+/// time moving away from `start`, each with its level (its distance from
+/// `start`). Within a level points come in colex order of the coordinates
+/// moved, so ascending and descending walks mirror each other. Each end
+/// steps to its colex neighbour, unranking only when it starts a level, and
+/// `position` can be saved and passed to `converge_from` to pick the walk up
+/// again.
+#[derive(Clone, Debug)]
+pub struct Converge<B> {
+    origin: B,
+    free: Vec<usize>,
+    descending: bool,
+    // Level and index within it of the next point from the front, and just
+    // past the next point from the back
+    front: (usize, u128),
+    back: (usize, u128),
+    // The points at `front` and just before `back`, once stepped to
+    ahead: Option<((usize, u128), Cursor<B>)>,
+    behind: Option<((usize, u128), Cursor<B>)>,
+}
+
+/// A point above an origin with the indices into `free` of the coordinates
+/// moved to reach it, ascending, so that it can step to its colex neighbours
+/// among the points the same distance from the origin.
+#[derive(Clone, Debug, PartialEq)]
+struct Cursor<B> {
+    moved: Vec<usize>,
+    point: B,
+}
+
+impl<B: Packed> Cursor<B> {
+    fn new(origin: &B, free: &[usize], moved: Vec<usize>) -> Self {
+        let mut point = origin.clone();
+
+        for j in &moved {
+            set(point.limbs_mut(), free[*j], true);
+        }
+
+        Self { moved, point }
+    }
+
+    /// The point of colex rank `rank` among those `k` steps from `origin`.
+    fn unrank(origin: &B, free: &[usize], k: usize, rank: u128) -> Option<Self> {
+        let mut moved = Vec::with_capacity(k);
+
+        subset_unrank(free.len(), k, rank, &mut moved)?;
+
+        Some(Self::new(origin, free, moved))
+    }
+
+    /// Moves to the next point in colex order, or the previous one, and
+    /// `false` if there is none. Like Gosper's hack this touches only the
+    /// lowest run of moved coordinates, so steps are amortized O(1).
+    fn step(&mut self, free: &[usize], successor: bool) -> bool {
+        let moved = &mut self.moved;
+        let limbs = self.point.limbs_mut();
+        let d = free.len();
+
+        // The lowest coordinate with room to move one place without meeting
+        // its neighbour; everything below it is packed against the bottom
+        let i = if successor {
+            (0..moved.len()).find(|&i| moved[i] + 1 < moved.get(i + 1).map_or(d, |m| *m))
+        } else {
+            (0..moved.len()).find(|&i| moved[i] > if i == 0 { 0 } else { moved[i - 1] + 1 })
+        };
+        let Some(i) = i else {
+            return false;
+        };
+
+        for j in &moved[..=i] {
+            set(limbs, free[*j], false);
+        }
+
+        // The run below `i` repacks at the bottom after a successor, and just
+        // under the new `moved[i]` after a predecessor
+        let top = if successor {
+            moved[i] + 1
+        } else {
+            moved[i] - 1
+        };
+        let bottom = if successor { 0 } else { top - i };
+
+        for (j, m) in moved[..i].iter_mut().enumerate() {
+            *m = bottom + j;
+        }
+
+        moved[i] = top;
+
+        for j in &moved[..=i] {
+            set(limbs, free[*j], true);
+        }
+
+        true
+    }
+}
+
+impl<B: Packed> Converge<B> {
+    pub(crate) fn new(start: &B, end: &B) -> Result<Self, IncomparableError> {
+        Self::resume(start, end, (0, 0))
+    }
+
+    pub(crate) fn resume(
+        start: &B,
+        end: &B,
+        position: (usize, u128),
+    ) -> Result<Self, IncomparableError> {
+        let (origin, free, descending) = span(start, end)?;
+
+        Ok(Self::build(origin, free, descending, position))
+    }
+
+    /// Walk upwards from `lo` to `hi`, which must lie below it.
+    pub(crate) fn between(lo: &B, hi: &B) -> Self {
+        Self::build(lo.clone(), differences(lo, hi), false, (0, 0))
+    }
+
+    fn build(origin: B, free: Vec<usize>, descending: bool, position: (usize, u128)) -> Self {
+        let mut converge = Self {
+            origin,
+            back: (free.len(), 1),
+            free,
+            descending,
+            front: position,
+            ahead: None,
+            behind: None,
+        };
+
+        converge.normalize_front();
+        converge
+    }
+
+    fn distance(&self) -> usize {
+        self.free.len()
+    }
+
+    fn level_size(&self, level: usize) -> u128 {
+        binomial(self.distance(), level)
+    }
+
+    fn normalize_front(&mut self) {
+        while self.front.0 <= self.distance() && self.front.1 >= self.level_size(self.front.0) {
+            self.front = (self.front.0 + 1, 0);
+        }
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.front >= self.back
+    }
+
+    fn cursor(&self, (level, index): (usize, u128)) -> Cursor<B> {
+        let d = self.distance();
+
+        // Complementing reverses colex order, so the layers of a descending
+        // walk are read from the back
+        let (k, rank) = if self.descending {
+            (d - level, self.level_size(level) - 1 - index)
+        } else {
+            (level, index)
+        };
+
+        Cursor::unrank(&self.origin, &self.free, k, rank).unwrap()
+    }
+
+    /// Number of points, including those already yielded, or `None` if that
+    /// overflows a `u128`.
+    pub fn total(&self) -> Option<u128> {
+        1_u128.checked_shl(self.distance() as u32)
+    }
+
+    /// Number of points not yet yielded, or `None` if that overflows a
+    /// `u128`.
+    pub fn remaining(&self) -> Option<u128> {
+        let ((front, i), (back, j)) = (self.front, self.back);
+        let d = self.distance();
+
+        if self.is_exhausted() {
+            Some(0)
+        } else if front == back {
+            Some(j - i)
+        } else {
+            (front + 1..back).try_fold(
+                (checked_binomial(d, front)? - i).checked_add(j)?,
+                |sum, level| sum.checked_add(checked_binomial(d, level)?),
+            )
+        }
+    }
+
+    /// Level and index within the level of the next point to be yielded.
+    pub fn position(&self) -> (usize, u128) {
+        self.front
+    }
+}
+
+impl<B: Packed> Iterator for Converge<B> {
+    type Item = (usize, B);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_exhausted() {
+            return None;
+        }
+
+        let position = self.front;
+        let mut cursor = match self.ahead.take() {
+            Some((ahead, cursor)) if ahead == position => cursor,
+            _ => self.cursor(position),
+        };
+        let point = cursor.point.clone();
+
+        self.front.1 += 1;
+        self.normalize_front();
+
+        if self.front.0 == position.0 && !self.is_exhausted() {
+            assert!(cursor.step(&self.free, !self.descending));
+            self.ahead = Some((self.front, cursor));
+        }
+
+        Some((position.0, point))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.remaining().map_or((usize::MAX, None), size_hint)
+    }
+}
+
+impl<B: Packed> DoubleEndedIterator for Converge<B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.is_exhausted() {
+            return None;
+        }
+
+        self.back.1 -= 1;
+
+        let position = self.back;
+        let mut cursor = match self.behind.take() {
+            Some((behind, cursor)) if behind == position => cursor,
+            _ => self.cursor(position),
+        };
+        let point = cursor.point.clone();
+
+        if self.back.1 == 0 && self.back.0 > 0 {
+            self.back = (self.back.0 - 1, self.level_size(self.back.0 - 1));
+        } else if self.back.1 > 0 && !self.is_exhausted() {
+            assert!(cursor.step(&self.free, self.descending));
+            self.behind = Some(((position.0, position.1 - 1), cursor));
+        }
+
+        Some((position.0, point))
+    }
+}
+
+impl<B: Packed> FusedIterator for Converge<B> {}
+
+/// Every maximal chain from `start` to `end`, in `path_rank` order, i.e.
+/// depth-first taking the lowest coordinate first.
+#[derive(Clone, Debug)]
+pub struct Paths<B> {
+    start: B,
+    front: Vec<usize>,
+    back: Vec<usize>,
+    // `None` once d! overflows, in which case the ends never meet in practice
+    remaining: Option<u128>,
+    done: bool,
+}
+
+impl<B: Packed> Paths<B> {
+    pub(crate) fn new(start: &B, end: &B) -> Result<Self, IncomparableError> {
+        let (_, order, _) = span(start, end)?;
+
+        Ok(Self::build(start.clone(), order))
+    }
+
+    /// Paths from `lo` up to `hi`, which must lie below it.
+    pub(crate) fn between(lo: &B, hi: &B) -> Self {
+        Self::build(lo.clone(), differences(lo, hi))
+    }
+
+    fn build(start: B, front: Vec<usize>) -> Self {
+        Self {
+            start,
+            back: front.iter().rev().copied().collect(),
+            remaining: checked_factorial(front.len()),
+            front,
+            done: false,
+        }
+    }
+
+    /// Number of paths, including those already yielded, or `None` if that
+    /// overflows a `u128`.
+    pub fn total(&self) -> Option<u128> {
+        checked_factorial(self.front.len())
+    }
+
+    fn path(&self, order: &[usize]) -> Vec<B> {
+        let mut cursor = self.start.clone();
+        let mut path = Vec::with_capacity(order.len() + 1);
+
+        path.push(cursor.clone());
+
+        for i in order {
+            flip(&mut cursor, *i);
+            path.push(cursor.clone());
+        }
+
+        path
+    }
+
+    fn take(&mut self, advanced: bool) {
+        self.done |= !advanced;
+
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+            self.done |= *remaining == 0;
+        }
+    }
+}
+
+impl<B: Packed> Iterator for Paths<B> {
+    type Item = Vec<B>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let path = self.path(&self.front);
+        let advanced = next_permutation(&mut self.front).is_some();
+
+        self.take(advanced);
+
+        Some(path)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining.map(usize::try_from) {
+            _ if self.done => (0, Some(0)),
+            Some(Ok(remaining)) => (remaining, Some(remaining)),
+            _ => (usize::MAX, None),
+        }
+    }
+}
+
+impl<B: Packed> DoubleEndedIterator for Paths<B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let path = self.path(&self.back);
+        let advanced = previous_permutation(&mut self.back).is_some();
+
+        self.take(advanced);
+
+        Some(path)
+    }
+}
+
+impl<B: Packed> FusedIterator for Paths<B> {}
+
+/// Steps `order` to the lexicographically next permutation, returning the
+/// first index that changed, or `None` if it was the last.
+pub(crate) fn next_permutation(order: &mut [usize]) -> Option<usize> {
+    // The last ascent, swapped with its least larger successor and followed
+    // by the reversed tail
+    let pivot = (1..order.len()).rev().find(|j| order[j - 1] < order[*j])? - 1;
+    let swap = (pivot + 1..order.len())
+        .rev()
+        .find(|j| order[*j] > order[pivot])
+        .unwrap();
+
+    order.swap(pivot, swap);
+    order[pivot + 1..].reverse();
+
+    Some(pivot)
+}
+
+/// Inverse of `next_permutation`.
+fn previous_permutation(order: &mut [usize]) -> Option<usize> {
+    let pivot = (1..order.len()).rev().find(|j| order[j - 1] > order[*j])? - 1;
+    let swap = (pivot + 1..order.len())
+        .rev()
+        .find(|j| order[*j] < order[pivot])
+        .unwrap();
+
+    order.swap(pivot, swap);
+    order[pivot + 1..].reverse();
+
+    Some(pivot)
+}
+
+/// One layer of an interval in colex order, walked from either end by
+/// stepping to colex neighbours. Layers small enough to count also know
+/// their length and can jump by unranking.
+#[derive(Clone, Debug)]
+pub struct Layer<B> {
+    origin: B,
+    free: Vec<usize>,
+    k: usize,
+    front: Cursor<B>,
+    back: Cursor<B>,
+    // Colex ranks of `front` and just past `back`, `None` once C(d, k)
+    // overflows
+    ranks: Option<(u128, u128)>,
+    done: bool,
+}
+
+impl<B: Packed> Layer<B> {
+    pub(crate) fn new(start: &B, end: &B, k: usize) -> Result<Self, IncomparableError> {
+        let (origin, free, _) = span(start, end)?;
+
+        Ok(Self::build(origin, free, k))
+    }
+
+    /// Points `k` steps above `lo` and below `hi`, which must lie above it.
+    pub(crate) fn between(lo: &B, hi: &B, k: usize) -> Self {
+        Self::build(lo.clone(), differences(lo, hi), k)
+    }
+
+    fn build(origin: B, free: Vec<usize>, k: usize) -> Self {
+        let d = free.len();
+        let done = k > d;
+        let (first, last) = if done {
+            (Vec::new(), Vec::new())
+        } else {
+            (Vec::from_iter(0..k), Vec::from_iter(d - k..d))
+        };
+
+        Self {
+            front: Cursor::new(&origin, &free, first),
+            back: Cursor::new(&origin, &free, last),
+            ranks: checked_binomial(d, k).map(|total| (0, total)),
+            origin,
+            free,
+            k,
+            done,
+        }
+    }
+
+    /// Number of points in the layer, including those already yielded, or
+    /// `None` if that overflows a `u128`.
+    pub fn total(&self) -> Option<u128> {
+        checked_binomial(self.free.len(), self.k)
+    }
+
+    /// Number of points not yet yielded, or `None` if that overflows a
+    /// `u128`.
+    pub fn remaining(&self) -> Option<u128> {
+        match self.ranks {
+            _ if self.done => Some(0),
+            Some((front, back)) => Some(back - front),
+            None => None,
+        }
+    }
+
+    /// The point of colex rank `rank`, independent of iteration progress.
+    pub fn get(&self, rank: u128) -> Option<B> {
+        Cursor::unrank(&self.origin, &self.free, self.k, rank).map(|cursor| cursor.point)
+    }
+}
+
+impl<B: Packed> Iterator for Layer<B> {
+    type Item = B;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let point = self.front.point.clone();
+
+        self.done = self.front == self.back;
+        self.front.step(&self.free, true);
+
+        if let Some((front, _)) = self.ranks.as_mut() {
+            *front += 1;
+        }
+
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.remaining().map_or((usize::MAX, None), size_hint)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let Some((front, back)) = self.ranks.filter(|_| !self.done && n > 0) else {
+            for _ in 0..n {
+                self.next()?;
+            }
+
+            return self.next();
+        };
+
+        // Jump straight to the point `n` ahead by unranking it
+        let front = front.saturating_add(n as u128).min(back);
+
+        self.ranks = Some((front, back));
+        self.done = front == back;
+
+        if !self.done {
+            self.front = Cursor::unrank(&self.origin, &self.free, self.k, front).unwrap();
+        }
+
+        self.next()
+    }
+}
+
+impl<B: Packed> DoubleEndedIterator for Layer<B> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let point = self.back.point.clone();
+
+        self.done = self.front == self.back;
+        self.back.step(&self.free, false);
+
+        if let Some((_, back)) = self.ranks.as_mut() {
+            *back -= 1;
+        }
+
+        Some(point)
+    }
+}
+
+impl<B: Packed> FusedIterator for Layer<B> {}
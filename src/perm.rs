@@ -0,0 +1,353 @@
+#![allow(dead_code)]
+
+use std::{error::Error, fmt, ops::Mul};
+
+use crate::{bits::Bits, function::MonotoneFunction, order::Lex};
+
+#[derive(Debug)]
+pub struct InvalidPermutationError;
+
+impl fmt::Display for InvalidPermutationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "InvalidPermutationError")
+    }
+}
+
+impl Error for InvalidPermutationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+/// Permutation of the coordinates `0..N`, sending `i` to `self.get(i)`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Permutation<const N: usize> {
+    map: [usize; N],
+}
+
+impl<const N: usize> Permutation<N> {
+    pub fn identity() -> Self {
+        let mut map = [0; N];
+
+        for (i, m) in map.iter_mut().enumerate() {
+            *m = i;
+        }
+
+        Self { map }
+    }
+
+    /// Builds a permutation from disjoint cycles, e.g. `&[&[0, 2, 1]]` sends
+    /// 0 to 2, 2 to 1 and 1 to 0.
+    pub fn from_cycles(cycles: &[&[usize]]) -> Result<Self, InvalidPermutationError> {
+        let mut map = Self::identity().map;
+        let mut seen = [false; N];
+
+        for cycle in cycles {
+            for (j, i) in cycle.iter().enumerate() {
+                if *i >= N || seen[*i] {
+                    return Err(InvalidPermutationError);
+                }
+
+                seen[*i] = true;
+                map[*i] = cycle[(j + 1) % cycle.len()];
+            }
+        }
+
+        Ok(Self { map })
+    }
+
+    pub fn get(&self, i: usize) -> usize {
+        self.map[i]
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.map.iter().enumerate().all(|(i, m)| i == *m)
+    }
+
+    /// Moves the bit at index `i` to index `self.get(i)`.
+    pub fn apply(&self, x: &Bits<N>) -> Bits<N> {
+        let mut y = Bits::new(false);
+
+        for i in x.ones() {
+            y.set(self.map[i], true);
+        }
+
+        y
+    }
+
+    /// `self` after `other`, so that applying the result is applying `other`
+    /// and then `self`.
+    pub fn compose(&self, other: &Permutation<N>) -> Permutation<N> {
+        let mut map = [0; N];
+
+        for (i, m) in map.iter_mut().enumerate() {
+            *m = self.map[other.map[i]];
+        }
+
+        Self { map }
+    }
+
+    pub fn inverse(&self) -> Permutation<N> {
+        let mut map = [0; N];
+
+        for (i, m) in self.map.iter().enumerate() {
+            map[*m] = i;
+        }
+
+        Self { map }
+    }
+
+    /// Cycles of length two or more, each starting at its least element.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut seen = [false; N];
+        let mut cycles = Vec::new();
+
+        for start in 0..N {
+            if seen[start] || self.map[start] == start {
+                continue;
+            }
+
+            let mut cycle = Vec::new();
+            let mut i = start;
+
+            while !seen[i] {
+                seen[i] = true;
+                cycle.push(i);
+                i = self.map[i];
+            }
+
+            cycles.push(cycle);
+        }
+
+        cycles
+    }
+}
+
+impl<const N: usize> TryFrom<[usize; N]> for Permutation<N> {
+    type Error = InvalidPermutationError;
+
+    fn try_from(map: [usize; N]) -> Result<Self, Self::Error> {
+        let mut seen = [false; N];
+
+        for m in map {
+            if m >= N || seen[m] {
+                return Err(InvalidPermutationError);
+            }
+
+            seen[m] = true;
+        }
+
+        Ok(Self { map })
+    }
+}
+
+impl<const N: usize> Mul for Permutation<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.compose(&rhs)
+    }
+}
+
+impl<const N: usize> fmt::Display for Permutation<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cycles = self.cycles();
+
+        if cycles.is_empty() {
+            return write!(f, "()");
+        }
+
+        for cycle in cycles {
+            let cycle: Vec<String> = cycle.iter().map(|i| i.to_string()).collect();
+
+            write!(f, "({})", cycle.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Individualisation search over the orderings of the coordinates. Placing
+/// coordinate `v` at position `p` fixes bit `p` of every relabelled
+/// implicant, and branches are compared by the sorted multiset of those
+/// prefixes, depth by depth, so the least leaf is a canonical relabelling.
+/// Leaves that tie with it yield automorphisms, which in turn prune
+/// branches that are images of ones already explored.
+struct Search<'a, const N: usize> {
+    f: &'a MonotoneFunction<N>,
+    implicants: Vec<Bits<N>>,
+    invariants: Vec<Vec<usize>>,
+    order: Vec<usize>,
+    best_keys: Vec<Option<Vec<Lex<Bits<N>>>>>,
+    best: Option<Permutation<N>>,
+    fresh: bool,
+    automorphisms: Vec<Permutation<N>>,
+}
+
+impl<'a, const N: usize> Search<'a, N> {
+    fn run(f: &'a MonotoneFunction<N>) -> Self {
+        let implicants: Vec<Bits<N>> = f.implicants().collect();
+        let mut invariants = vec![vec![0; N + 1]; N];
+
+        // How many implicants of each weight mention a coordinate is the same
+        // for a coordinate and its image under any relabelling
+        for a in &implicants {
+            for i in a.ones() {
+                invariants[i][a.count_ones()] += 1;
+            }
+        }
+
+        let mut search = Self {
+            f,
+            implicants,
+            invariants,
+            order: Vec::with_capacity(N),
+            best_keys: vec![None; N + 1],
+            best: None,
+            fresh: true,
+            automorphisms: Vec::new(),
+        };
+        let prefixes = vec![Bits::new(false); search.implicants.len()];
+
+        search.descend(&prefixes);
+        search
+    }
+
+    fn leaf(&mut self) {
+        let mut map = [0; N];
+
+        for (p, v) in self.order.iter().enumerate() {
+            map[*v] = p;
+        }
+
+        let leaf = Permutation { map };
+
+        match self.best {
+            Some(best) if !self.fresh => {
+                let automorphism = best.inverse().compose(&leaf);
+
+                if !automorphism.is_identity()
+                    && !self.automorphisms.contains(&automorphism)
+                    && self.f.permute(&automorphism) == *self.f
+                {
+                    self.automorphisms.push(automorphism);
+                }
+            }
+            _ => {
+                self.best = Some(leaf);
+                self.fresh = false;
+            }
+        }
+    }
+
+    /// Whether `v` lies in the orbit of an already explored sibling under the
+    /// automorphisms found so far that fix the current prefix.
+    fn is_pruned(&self, v: usize, explored: &[usize]) -> bool {
+        let mut parent: Vec<usize> = (0..N).collect();
+
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+
+            i
+        }
+
+        for gamma in &self.automorphisms {
+            if self.order.iter().all(|u| gamma.get(*u) == *u) {
+                for i in 0..N {
+                    let (a, b) = (root(&mut parent, i), root(&mut parent, gamma.get(i)));
+                    parent[a] = b;
+                }
+            }
+        }
+
+        let r = root(&mut parent, v);
+
+        explored.iter().any(|u| root(&mut parent, *u) == r)
+    }
+
+    fn descend(&mut self, prefixes: &[Bits<N>]) {
+        let depth = self.order.len();
+
+        if depth == N {
+            self.leaf();
+
+            return;
+        }
+
+        let unplaced: Vec<usize> = (0..N).filter(|v| !self.order.contains(v)).collect();
+        let least = unplaced
+            .iter()
+            .map(|v| &self.invariants[*v])
+            .min()
+            .unwrap()
+            .clone();
+        let candidates: Vec<usize> = unplaced
+            .into_iter()
+            .filter(|v| self.invariants[*v] == least)
+            .collect();
+        let mut explored = Vec::new();
+
+        for v in candidates {
+            if self.is_pruned(v, &explored) {
+                continue;
+            }
+
+            explored.push(v);
+
+            let children: Vec<Bits<N>> = prefixes
+                .iter()
+                .zip(self.implicants.iter())
+                .map(|(prefix, a)| {
+                    let mut child = *prefix;
+                    child.set(depth, a.get(v));
+                    child
+                })
+                .collect();
+            let mut key: Vec<Lex<Bits<N>>> = children.iter().map(|c| Lex(*c)).collect();
+
+            key.sort();
+
+            match &self.best_keys[depth + 1] {
+                Some(best) if key > *best => continue,
+                Some(best) if key == *best => {}
+                _ => {
+                    self.best_keys[depth + 1] = Some(key);
+
+                    for stale in self.best_keys.iter_mut().skip(depth + 2) {
+                        *stale = None;
+                    }
+
+                    self.fresh = true;
+                }
+            }
+
+            self.order.push(v);
+            self.descend(&children);
+            self.order.pop();
+        }
+    }
+}
+
+impl<const N: usize> MonotoneFunction<N> {
+    /// Relabels the inputs so that input `i` becomes input `pi.get(i)`.
+    pub fn permute(&self, pi: &Permutation<N>) -> MonotoneFunction<N> {
+        MonotoneFunction::new(self.implicants().map(|a| pi.apply(&a)).collect())
+    }
+
+    /// Canonical representative of the orbit of `self` under relabelling of
+    /// its inputs, with a permutation taking `self` to it. Functions in the
+    /// same orbit have equal canonical forms.
+    pub fn canonical(&self) -> (MonotoneFunction<N>, Permutation<N>) {
+        let pi = Search::run(self).best.unwrap();
+
+        (self.permute(&pi), pi)
+    }
+
+    /// Generators of the group of relabellings that leave `self` unchanged.
+    pub fn automorphisms(&self) -> Vec<Permutation<N>> {
+        Search::run(self).automorphisms
+    }
+}
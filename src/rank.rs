@@ -0,0 +1,172 @@
+#![allow(dead_code)]
+
+use crate::{
+    bits::{BitOrder, Bits, IncomparableError},
+    packed,
+    util::{binomial, checked_binomial, factorial},
+};
+
+/// Colex rank of the `k`-subset of `0..` given by the ascending `positions`.
+/// Each term is at most the rank, so this panics only if the rank itself
+/// overflows a `u128`.
+fn subset_rank(positions: impl Iterator<Item = usize>) -> u128 {
+    positions
+        .enumerate()
+        .try_fold(0_u128, |rank, (j, c)| {
+            rank.checked_add(checked_binomial(c, j + 1)?)
+        })
+        .expect("layer rank overflows u128")
+}
+
+/// Inverse of `subset_rank` over `k`-subsets of `0..d`, written into `out`
+/// in ascending order. `None` if `rank` is out of range, which it never is
+/// once `C(d, k)` overflows a `u128`.
+pub(crate) fn subset_unrank(
+    d: usize,
+    k: usize,
+    mut rank: u128,
+    out: &mut Vec<usize>,
+) -> Option<()> {
+    if checked_binomial(d, k).is_some_and(|total| rank >= total) {
+        return None;
+    }
+
+    out.clear();
+
+    let mut c = d;
+
+    for i in (1..=k).rev() {
+        c -= 1;
+
+        // A coefficient too large to hold is certainly above `rank`
+        while checked_binomial(c, i).is_none_or(|b| b > rank) {
+            c -= 1;
+        }
+
+        rank -= binomial(c, i);
+        out.push(c);
+    }
+
+    out.reverse();
+
+    Some(())
+}
+
+impl<const N: usize> Bits<N> {
+    /// Position in the colex order of the whole cube, which is the value read
+    /// with `BitOrder::LsbFirst`. Panics if a bit at index 128 or above is
+    /// set; wider values that are zero there rank like their low 128 bits.
+    pub fn rank(&self) -> u128 {
+        self.to_uint(BitOrder::LsbFirst)
+            .expect("rank is limited to 128 bits")
+    }
+
+    pub fn unrank(rank: u128) -> Option<Self> {
+        Self::from_uint(rank, BitOrder::LsbFirst).ok()
+    }
+
+    /// Position among the points of the same weight in colex order, via the
+    /// combinatorial number system. Panics if the rank overflows a `u128`,
+    /// which takes a layer of more than `2^128` points.
+    pub fn layer_rank(&self) -> u128 {
+        subset_rank(self.ones())
+    }
+
+    pub fn layer_unrank(k: usize, rank: u128) -> Option<Self> {
+        Self::layer(k).get(rank)
+    }
+
+    /// `layer_rank` relative to the interval spanned by `a` and `b`, in which
+    /// only the coordinates where they differ are free. `None` if `self` lies
+    /// outside the interval, and panics like `layer_rank` if the rank
+    /// overflows a `u128`.
+    pub fn interval_layer_rank(&self, a: &Bits<N>, b: &Bits<N>) -> Option<u128> {
+        let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+
+        if !(lo <= self && self <= hi) {
+            return None;
+        }
+
+        let raised = *self ^ *lo;
+        let positions = (*lo ^ *hi)
+            .ones()
+            .enumerate()
+            .filter(|(_, i)| raised.get(*i))
+            .map(|(j, _)| j);
+
+        Some(subset_rank(positions))
+    }
+
+    /// Inverse of `interval_layer_rank` among the points `k` steps above the
+    /// bottom of the interval spanned by `self` and `other`.
+    pub fn interval_layer_unrank(&self, other: &Bits<N>, k: usize, rank: u128) -> Option<Self> {
+        self.interval_layer(other, k).ok()?.get(rank)
+    }
+
+    /// Position of a maximal chain among those between its ends, ordered
+    /// lexicographically by the sequence of coordinates flipped along it (its
+    /// Lehmer code). `None` if `path` is not a maximal chain.
+    pub fn path_rank(path: &[Bits<N>]) -> Option<u128> {
+        let (start, end) = (path.first()?, path.last()?);
+        let d = start.distance(end).ok()?;
+
+        if path.len() != d + 1 {
+            return None;
+        }
+
+        let mut unflipped = Vec::from_iter((*start ^ *end).ones());
+        let mut rank = 0;
+
+        for (j, pair) in path.windows(2).enumerate() {
+            let flip = pair[0] ^ pair[1];
+            let position = unflipped
+                .iter()
+                .position(|i| flip.count_ones() == 1 && flip.get(*i))?;
+
+            unflipped.remove(position);
+            rank += position as u128 * factorial(d - 1 - j);
+        }
+
+        Some(rank)
+    }
+
+    /// Inverse of `path_rank` among the maximal chains from `self` to
+    /// `other`.
+    pub fn path_unrank(&self, other: &Bits<N>, mut rank: u128) -> Option<Vec<Bits<N>>> {
+        let d = self.distance(other).ok()?;
+
+        if rank >= factorial(d) {
+            return None;
+        }
+
+        let mut unflipped = Vec::from_iter((*self ^ *other).ones());
+        let mut cursor = *self;
+        let mut path = vec![cursor];
+
+        for j in 0..d {
+            let f = factorial(d - 1 - j);
+            let i = unflipped.remove((rank / f) as usize);
+
+            rank %= f;
+            cursor.set(i, !cursor.get(i));
+            path.push(cursor);
+        }
+
+        Some(path)
+    }
+
+    /// Points of weight `k` in colex order. Layers of more than `u128::MAX`
+    /// points have no `total()` but are still walked from both ends.
+    pub fn layer(k: usize) -> Layer<N> {
+        packed::Layer::new(&Self::new(false), &Self::new(true), k).unwrap()
+    }
+
+    /// Points `k` steps above the bottom of the interval spanned by `self`
+    /// and `other`, whichever way round they are given, in colex order of
+    /// their free coordinates. As with `layer` there is no size limit.
+    pub fn interval_layer(&self, other: &Bits<N>, k: usize) -> Result<Layer<N>, IncomparableError> {
+        packed::Layer::new(self, other, k)
+    }
+}
+
+pub type Layer<const N: usize> = packed::Layer<Bits<N>>;
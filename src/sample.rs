@@ -0,0 +1,156 @@
+#![allow(dead_code)]
+
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    seq::SliceRandom,
+    Rng,
+};
+
+use crate::{bits::Bits, interval::Interval, util::binomial};
+
+/// Uniform point of `interval` lying `k` steps above its bottom, or `None`
+/// if the interval has fewer than `k` free coordinates.
+pub fn layer<const N: usize>(
+    interval: &Interval<N>,
+    k: usize,
+    rng: &mut impl Rng,
+) -> Option<Bits<N>> {
+    let free = Vec::from_iter(interval.free().ones());
+
+    if k > free.len() {
+        return None;
+    }
+
+    let mut x = interval.lo();
+
+    for i in free.choose_multiple(rng, k) {
+        x.set(*i, true);
+    }
+
+    Some(x)
+}
+
+/// Point of `interval` whose free coordinates are set independently with
+/// probability `p`.
+pub fn biased<const N: usize>(interval: &Interval<N>, p: f64, rng: &mut impl Rng) -> Bits<N> {
+    let mut x = interval.lo();
+
+    for i in interval.free().ones() {
+        if rng.gen_bool(p) {
+            x.set(i, true);
+        }
+    }
+
+    x
+}
+
+/// Uniform maximal chain of `interval`, from its bottom to its top.
+pub fn chain<const N: usize>(interval: &Interval<N>, rng: &mut impl Rng) -> Vec<Bits<N>> {
+    let mut free = Vec::from_iter(interval.free().ones());
+    let mut x = interval.lo();
+    let mut chain = vec![x];
+
+    free.shuffle(rng);
+
+    for i in free {
+        x.set(i, true);
+        chain.push(x);
+    }
+
+    chain
+}
+
+/// Uniform antichain of `size` points of `interval`, or `None` if none
+/// exists. Draws points until two are comparable and then starts over, so
+/// it is meant for small intervals or sizes well below the width.
+pub fn antichain<const N: usize>(
+    interval: &Interval<N>,
+    size: usize,
+    rng: &mut impl Rng,
+) -> Option<Vec<Bits<N>>> {
+    let d = interval.dimension();
+
+    if binomial(d, d / 2) < size as u128 {
+        return None;
+    }
+
+    let mut members = Vec::with_capacity(size);
+
+    while members.len() < size {
+        let x = biased(interval, 0.5, rng);
+
+        if members.contains(&x) {
+            continue;
+        } else if members
+            .iter()
+            .any(|y: &Bits<N>| y.partial_cmp(&x).is_some())
+        {
+            members.clear();
+        } else {
+            members.push(x);
+        }
+    }
+
+    Some(members)
+}
+
+/// Uniform antichain of `size` points all lying `k` steps above the bottom
+/// of `interval`, or `None` if that layer has fewer points.
+pub fn layer_antichain<const N: usize>(
+    interval: &Interval<N>,
+    k: usize,
+    size: usize,
+    rng: &mut impl Rng,
+) -> Option<Vec<Bits<N>>> {
+    let layer = interval.lo().interval_layer(&interval.hi(), k).unwrap();
+
+    if layer.total().is_some_and(|total| total < size as u128) {
+        return None;
+    }
+
+    let mut members = Vec::with_capacity(size);
+
+    while members.len() < size {
+        let x = self::layer(interval, k, rng).unwrap();
+
+        if !members.contains(&x) {
+            members.push(x);
+        }
+    }
+
+    Some(members)
+}
+
+/// Uniform member of the up-set generated by `generators`, or `None` if
+/// there are none. Picks a generator in proportion to the size of its upper
+/// shadow and a point above it, then accepts with probability one over the
+/// number of generators below that point (Karp and Luby).
+pub fn up_set<const N: usize>(generators: &[Bits<N>], rng: &mut impl Rng) -> Option<Bits<N>> {
+    let intervals: Vec<Interval<N>> = generators.iter().map(Interval::upper_shadow).collect();
+
+    union(&intervals, rng)
+}
+
+/// Uniform member of the down-set generated by `generators`, as `up_set`.
+pub fn down_set<const N: usize>(generators: &[Bits<N>], rng: &mut impl Rng) -> Option<Bits<N>> {
+    let intervals: Vec<Interval<N>> = generators.iter().map(Interval::lower_shadow).collect();
+
+    union(&intervals, rng)
+}
+
+fn union<const N: usize>(intervals: &[Interval<N>], rng: &mut impl Rng) -> Option<Bits<N>> {
+    let largest = intervals.iter().map(|i| i.dimension()).max()?;
+    let weights = intervals
+        .iter()
+        .map(|i| 0.5_f64.powi((largest - i.dimension()) as i32));
+    let index = WeightedIndex::new(weights).unwrap();
+
+    loop {
+        let x = biased(&intervals[index.sample(rng)], 0.5, rng);
+        let covering = intervals.iter().filter(|i| i.contains(&x)).count();
+
+        if rng.gen_range(0..covering) == 0 {
+            return Some(x);
+        }
+    }
+}
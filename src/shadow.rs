@@ -0,0 +1,95 @@
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+
+use crate::{
+    bits::Bits,
+    order::Colex,
+    util::{binomial, checked_binomial},
+};
+
+fn sorted<const N: usize>(family: HashSet<Bits<N>>) -> Vec<Bits<N>> {
+    let mut family: Vec<Colex<Bits<N>>> = family.into_iter().map(Colex).collect();
+
+    family.sort();
+    family.into_iter().map(|x| x.0).collect()
+}
+
+/// Every set obtained by removing one element from a member of `family`,
+/// once each, in colex order. For a family of `k`-sets these are the
+/// `(k - 1)`-sets covered by some member.
+pub fn shadow<const N: usize>(family: &[Bits<N>]) -> Vec<Bits<N>> {
+    sorted(family.iter().flat_map(|x| x.horizon(true)).collect())
+}
+
+/// Every set obtained by adding one element to a member of `family`, once
+/// each, in colex order.
+pub fn shade<const N: usize>(family: &[Bits<N>]) -> Vec<Bits<N>> {
+    sorted(family.iter().flat_map(|x| x.horizon(false)).collect())
+}
+
+/// The `k`-cascade representation of `m`, i.e. the unique
+/// `m = C(a_k, k) + C(a_(k-1), k - 1) + ... + C(a_t, t)` with
+/// `a_k > a_(k-1) > ... > a_t >= t >= 1`, as the pairs `(a_i, i)`.
+pub fn cascade(mut m: u128, k: usize) -> Vec<(usize, usize)> {
+    let mut terms = Vec::new();
+
+    for i in (1..=k).rev() {
+        if m == 0 {
+            break;
+        }
+
+        // Largest a with C(a, i) <= m, which lies below i + m
+        let (mut lo, mut hi) = (i, (i as u128 + m).min(usize::MAX as u128) as usize);
+
+        while lo < hi {
+            let mid = lo + (hi - lo).div_ceil(2);
+
+            match checked_binomial(mid, i) {
+                Some(c) if c <= m => lo = mid,
+                _ => hi = mid - 1,
+            }
+        }
+
+        m -= binomial(lo, i);
+        terms.push((lo, i));
+    }
+
+    terms
+}
+
+/// Kruskal–Katona: the least possible size of the shadow of `m` sets of
+/// size `k`, attained by the first `m` of them in colex order.
+pub fn kruskal_katona(m: u128, k: usize) -> u128 {
+    cascade(m, k)
+        .into_iter()
+        .map(|(a, i)| binomial(a, i - 1))
+        .sum()
+}
+
+/// Whether some down-set of subsets of `0..n` has exactly `profile[k]`
+/// members of size `k` for each `k`, its f-vector. By Kruskal–Katona this
+/// holds exactly when each level fits and casts a shadow no larger than the
+/// level below.
+pub fn is_down_set_profile(profile: &[u128], n: usize) -> bool {
+    profile
+        .iter()
+        .enumerate()
+        .all(|(k, f)| *f <= binomial(n, k) && (k == 0 || kruskal_katona(*f, k) <= profile[k - 1]))
+}
+
+/// A down-set with the given f-vector, made of the initial colex segment of
+/// each level, or `None` if there is none.
+pub fn down_set_with_profile<const N: usize>(profile: &[u128]) -> Option<Vec<Bits<N>>> {
+    if !is_down_set_profile(profile, N) {
+        return None;
+    }
+
+    Some(
+        profile
+            .iter()
+            .enumerate()
+            .flat_map(|(k, f)| Bits::<N>::layer(k).take(*f as usize))
+            .collect(),
+    )
+}
@@ -0,0 +1,272 @@
+#![allow(dead_code)]
+
+use std::ops::{BitAnd, BitOr, Not};
+
+use crate::{bits::Bits, function::MonotoneFunction};
+
+/// Words with a one at each position whose index has bit `i` clear, for
+/// the coordinates `i < 6` that live inside a single word.
+const LOW_HALVES: [u64; 6] = [
+    0x5555_5555_5555_5555,
+    0x3333_3333_3333_3333,
+    0x0f0f_0f0f_0f0f_0f0f,
+    0x00ff_00ff_00ff_00ff,
+    0x0000_ffff_0000_ffff,
+    0x0000_0000_ffff_ffff,
+];
+
+/// Function on the whole cube held as `2^N` bits, one per point, indexed by
+/// `Bits::rank`. Whole-function operations work on 64 points per word.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TruthTable<const N: usize> {
+    words: Vec<u64>,
+}
+
+impl<const N: usize> TruthTable<N> {
+    /// The constant function. Panics for `N > 32`.
+    pub fn new(value: bool) -> Self {
+        assert!(N <= 32, "truth tables are limited to 32 bits");
+
+        let mut table = Self {
+            words: vec![if value { u64::MAX } else { 0 }; (1_usize << N).div_ceil(64)],
+        };
+
+        table.mask();
+        table
+    }
+
+    pub fn from_fn(f: impl Fn(Bits<N>) -> bool) -> Self {
+        let mut table = Self::new(false);
+
+        for index in 0..1_usize << N {
+            if f(Bits::unrank(index as u128).unwrap()) {
+                table.words[index / 64] |= 1 << (index % 64);
+            }
+        }
+
+        table
+    }
+
+    /// Clears the unused high bits of a table smaller than one word.
+    fn mask(&mut self) {
+        if N < 6 {
+            self.words[0] &= (1 << (1 << N)) - 1;
+        }
+    }
+
+    fn index(x: &Bits<N>) -> usize {
+        x.rank() as usize
+    }
+
+    pub fn call(&self, x: Bits<N>) -> bool {
+        let index = Self::index(&x);
+
+        self.words[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    pub fn set(&mut self, x: Bits<N>, value: bool) {
+        let index = Self::index(&x);
+
+        if value {
+            self.words[index / 64] |= 1 << (index % 64);
+        } else {
+            self.words[index / 64] &= !(1 << (index % 64));
+        }
+    }
+
+    /// Number of true points.
+    pub fn count_ones(&self) -> u64 {
+        self.words.iter().map(|w| w.count_ones() as u64).sum()
+    }
+
+    /// Number of false points.
+    pub fn count_zeroes(&self) -> u64 {
+        (1 << N) - self.count_ones()
+    }
+
+    /// True points in rank order.
+    pub fn ones(&self) -> impl Iterator<Item = Bits<N>> + '_ {
+        self.words.iter().enumerate().flat_map(|(w, word)| {
+            (0..64)
+                .filter(move |b| word >> b & 1 == 1)
+                .map(move |b| Bits::unrank((w * 64 + b) as u128).unwrap())
+        })
+    }
+
+    /// Applies `f` to each pair of words holding the points without and with
+    /// coordinate `i`, as masks over the positions of the former.
+    fn pairs(&mut self, i: usize, f: impl Fn(u64, u64) -> (u64, u64)) {
+        if i < 6 {
+            let shift = 1 << i;
+
+            for word in self.words.iter_mut() {
+                let lo = *word & LOW_HALVES[i];
+                let hi = (*word >> shift) & LOW_HALVES[i];
+                let (lo, hi) = f(lo, hi);
+
+                *word = (lo & LOW_HALVES[i]) | ((hi & LOW_HALVES[i]) << shift);
+            }
+        } else {
+            let stride = 1 << (i - 6);
+
+            for w in (0..self.words.len()).filter(|w| w & stride == 0) {
+                let (lo, hi) = f(self.words[w], self.words[w + stride]);
+
+                self.words[w] = lo;
+                self.words[w + stride] = hi;
+            }
+        }
+    }
+
+    /// A pair `(x, y)` with `y` covering `x`, `x` true and `y` false, or
+    /// `None` if the function is monotone.
+    pub fn monotonicity_violation(&self) -> Option<(Bits<N>, Bits<N>)> {
+        for i in 0..N {
+            let mut violations = self.clone();
+
+            violations.pairs(i, |lo, hi| (lo & !hi, 0));
+
+            let first = violations.ones().next();
+
+            if let Some(x) = first {
+                let mut y = x;
+
+                y.set(i, true);
+
+                return Some((x, y));
+            }
+        }
+
+        None
+    }
+
+    pub fn is_monotone(&self) -> bool {
+        self.monotonicity_violation().is_none()
+    }
+
+    /// Least monotone function at or above `self`: true wherever some true
+    /// point lies below, by the subset-sum (zeta) transform over `or`.
+    pub fn up_closure(&self) -> TruthTable<N> {
+        let mut table = self.clone();
+
+        for i in 0..N {
+            table.pairs(i, |lo, hi| (lo, hi | lo));
+        }
+
+        table
+    }
+
+    /// Function true wherever some true point lies above.
+    pub fn down_closure(&self) -> TruthTable<N> {
+        let mut table = self.clone();
+
+        for i in 0..N {
+            table.pairs(i, |lo, hi| (lo | hi, hi));
+        }
+
+        table
+    }
+
+    /// Minimal true points of the up-closure as a `MonotoneFunction`, so for
+    /// a monotone table the function it represents.
+    pub fn minimal_true_points(&self) -> MonotoneFunction<N> {
+        let closure = self.up_closure();
+        let mut minimal = closure.clone();
+
+        // A point is minimal unless it stays true with some coordinate
+        // cleared
+        for i in 0..N {
+            let mut lower = closure.clone();
+
+            lower.pairs(i, |lo, _| (0, lo));
+            minimal = &minimal & &!&lower;
+        }
+
+        MonotoneFunction::new(minimal.ones().collect())
+    }
+}
+
+impl<const N: usize> From<&MonotoneFunction<N>> for TruthTable<N> {
+    fn from(f: &MonotoneFunction<N>) -> Self {
+        let mut table = Self::new(false);
+
+        for a in f.implicants() {
+            table.set(a, true);
+        }
+
+        table.up_closure()
+    }
+}
+
+impl<const N: usize> From<MonotoneFunction<N>> for TruthTable<N> {
+    fn from(f: MonotoneFunction<N>) -> Self {
+        Self::from(&f)
+    }
+}
+
+impl<const N: usize> BitAnd for &TruthTable<N> {
+    type Output = TruthTable<N>;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        TruthTable {
+            words: self
+                .words
+                .iter()
+                .zip(&rhs.words)
+                .map(|(a, b)| a & b)
+                .collect(),
+        }
+    }
+}
+
+impl<const N: usize> BitAnd for TruthTable<N> {
+    type Output = TruthTable<N>;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        &self & &rhs
+    }
+}
+
+impl<const N: usize> BitOr for &TruthTable<N> {
+    type Output = TruthTable<N>;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        TruthTable {
+            words: self
+                .words
+                .iter()
+                .zip(&rhs.words)
+                .map(|(a, b)| a | b)
+                .collect(),
+        }
+    }
+}
+
+impl<const N: usize> BitOr for TruthTable<N> {
+    type Output = TruthTable<N>;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        &self | &rhs
+    }
+}
+
+impl<const N: usize> Not for &TruthTable<N> {
+    type Output = TruthTable<N>;
+
+    fn not(self) -> Self::Output {
+        let mut table = TruthTable {
+            words: self.words.iter().map(|a| !a).collect(),
+        };
+
+        table.mask();
+        table
+    }
+}
+
+impl<const N: usize> Not for TruthTable<N> {
+    type Output = TruthTable<N>;
+
+    fn not(self) -> Self::Output {
+        !&self
+    }
+}
@@ -1,12 +1,21 @@
+#![allow(dead_code)]
+
 use rand::{thread_rng, Rng};
 use std::{collections::HashSet, hash::Hash};
 
 /// https://stackoverflow.com/questions/2394246/algorithm-to-select-a-single-random-combination-of-values
 pub fn rand_combination<T: Copy + Eq + Hash + PartialEq>(xs: &[T], k: usize) -> HashSet<T> {
+    rand_combination_with(xs, k, &mut thread_rng())
+}
+
+pub fn rand_combination_with<T: Copy + Eq + Hash + PartialEq>(
+    xs: &[T],
+    k: usize,
+    rng: &mut impl Rng,
+) -> HashSet<T> {
     assert!(xs.len() >= k);
 
     let n = xs.len();
-    let mut rng = thread_rng();
     let mut ys = HashSet::<T>::new();
 
     for j in n - k..n {
@@ -21,3 +30,49 @@ pub fn rand_combination<T: Copy + Eq + Hash + PartialEq>(xs: &[T], k: usize) ->
 
     ys
 }
+
+/// Binomial coefficient `n` choose `k`. Panics if it does not fit in a `u128`.
+pub fn binomial(n: usize, k: usize) -> u128 {
+    checked_binomial(n, k).expect("binomial coefficient overflows u128")
+}
+
+/// Binomial coefficient `n` choose `k`, or `None` if it does not fit in a
+/// `u128`.
+pub fn checked_binomial(n: usize, k: usize) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+
+    let k = k.min(n - k);
+    let mut c = 1_u128;
+
+    for i in 0..k {
+        // c * (n - i) is (i + 1) * C(n, i + 1), so once the common factor of
+        // c and i + 1 is taken out what is left of i + 1 divides n - i, and
+        // the product only overflows if C(n, i + 1) does
+        let (m, j) = ((n - i) as u128, (i + 1) as u128);
+        let g = gcd(c, j);
+
+        c = (c / g).checked_mul(m / (j / g))?;
+    }
+
+    Some(c)
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// `n!`. Panics if it does not fit in a `u128`, i.e. for `n > 34`.
+pub fn factorial(n: usize) -> u128 {
+    checked_factorial(n).expect("factorial overflows u128")
+}
+
+/// `n!`, or `None` if it does not fit in a `u128`.
+pub fn checked_factorial(n: usize) -> Option<u128> {
+    (1..=n as u128).try_fold(1_u128, |f, i| f.checked_mul(i))
+}